@@ -0,0 +1,79 @@
+use axum::http::{header, HeaderMap};
+use std::env;
+use std::sync::Arc;
+
+// Bearer token for admin-only endpoints, read from ADMIN_TOKEN. Unset or
+// empty means those endpoints aren't registered at all.
+#[derive(Clone, Default)]
+pub struct AdminToken(Option<Arc<String>>);
+
+impl AdminToken {
+    pub fn from_env() -> Self {
+        Self::new(env::var("ADMIN_TOKEN").ok())
+    }
+
+    pub fn new(token: Option<String>) -> Self {
+        let token = token
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty());
+        AdminToken(token.map(Arc::new))
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.0.is_some()
+    }
+
+    // Whether the request carries `Authorization: Bearer <ADMIN_TOKEN>`
+    pub fn authorizes(&self, headers: &HeaderMap) -> bool {
+        let Some(expected) = &self.0 else {
+            return false;
+        };
+        let provided = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, token)| token.trim());
+        provided.is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes()))
+    }
+}
+
+// Compare without returning early on the first differing byte, so response
+// timing doesn't reveal how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bearer(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn only_the_configured_token_is_accepted() {
+        let token = AdminToken::new(Some("s3cret".to_string()));
+        assert!(token.authorizes(&bearer("Bearer s3cret")));
+        assert!(token.authorizes(&bearer("bearer s3cret")));
+        assert!(!token.authorizes(&bearer("Bearer s3cre")));
+        assert!(!token.authorizes(&bearer("Bearer s3cret2")));
+        assert!(!token.authorizes(&bearer("Basic s3cret")));
+        assert!(!token.authorizes(&bearer("s3cret")));
+        assert!(!token.authorizes(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn unset_tokens_authorize_nothing() {
+        for token in [
+            AdminToken::new(None),
+            AdminToken::new(Some("  ".to_string())),
+        ] {
+            assert!(!token.is_configured());
+            assert!(!token.authorizes(&bearer("Bearer ")));
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use futures_util::StreamExt;
+use mongodb::{bson::Document, Database};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+// In-memory cache for derived listing data (ETags keyed by filter).
+// Only enabled while the change stream watcher is running, since without it
+// entries could go stale indefinitely.
+#[derive(Default)]
+pub struct ListingCache {
+    enabled: AtomicBool,
+    etags: RwLock<HashMap<String, String>>,
+}
+
+impl ListingCache {
+    pub fn get_etag(&self, key: &str) -> Option<String> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.etags.read().ok()?.get(key).cloned()
+    }
+
+    pub fn put_etag(&self, key: String, etag: String) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Ok(mut etags) = self.etags.write() {
+            etags.insert(key, etag);
+        }
+    }
+
+    pub fn invalidate(&self) {
+        if let Ok(mut etags) = self.etags.write() {
+            etags.clear();
+        }
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        self.invalidate();
+    }
+}
+
+// Watch the games collection and drop cached entries on any change.
+// Standalone mongod deployments don't support change streams, in which case
+// caching simply stays disabled.
+pub fn spawn_invalidation_watcher(db: Database, cache: Arc<ListingCache>) {
+    tokio::spawn(async move {
+        let games = db.collection::<Document>("games");
+
+        let mut stream = match games.watch().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!(
+                    "Warning: change streams unavailable, listing cache disabled: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        println!("Watching games collection for cache invalidation");
+        cache.set_enabled(true);
+
+        while let Some(event) = stream.next().await {
+            match event {
+                Ok(change) => {
+                    println!("[CACHE] Invalidating on {:?} change", change.operation_type);
+                    cache.invalidate();
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Warning: change stream failed, listing cache disabled: {}",
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+
+        cache.set_enabled(false);
+    });
+}
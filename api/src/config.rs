@@ -0,0 +1,13 @@
+use std::env;
+
+// Numeric setting from the environment, falling back to `default` when it's
+// unset or doesn't parse
+pub fn env_number<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+            eprintln!("Ignoring invalid {}: {}", name, raw);
+            default
+        }),
+        Err(_) => default,
+    }
+}
@@ -1,4 +1,11 @@
-use mongodb::{Client, Database};
+use futures_util::StreamExt;
+use mongodb::{
+    bson::{doc, Document},
+    options::{IndexOptions, ReturnDocument},
+    Client, Database, IndexModel,
+};
+
+use crate::models::normalized_name;
 use std::env;
 
 pub async fn connect() -> Result<Database, mongodb::error::Error> {
@@ -12,3 +19,305 @@ pub async fn connect() -> Result<Database, mongodb::error::Error> {
 
     Ok(client.database(&database_name))
 }
+
+// Every index the API uses, by collection. Unique indexes enforce the
+// one-per-wallet and one-name-per-creator rules, so startup fails without
+// them; the rest only speed up queries.
+fn indexes() -> Vec<(&'static str, IndexModel)> {
+    let unique = || IndexOptions::builder().unique(true).build();
+    vec![
+        // Text index backing relevance-scored search
+        (
+            "games",
+            IndexModel::builder()
+                .keys(doc! { "name": "text", "description": "text" })
+                .build(),
+        ),
+        // Multikey index for tag filters
+        (
+            "games",
+            IndexModel::builder().keys(doc! { "tags": 1 }).build(),
+        ),
+        // Library lookups by candy machine
+        (
+            "games",
+            IndexModel::builder()
+                .keys(doc! { "candy_machine_address": 1 })
+                .build(),
+        ),
+        // Anchored prefix matches for suggestions
+        (
+            "games",
+            IndexModel::builder()
+                .keys(doc! { "name_normalized": 1 })
+                .build(),
+        ),
+        // One game per name per creator, except explicit reissues. Games have
+        // no soft-delete flag, so every stored game is live and the filter
+        // only needs to exempt reissues. Games that
+        // predate the reissue flag are left out and only checked by create_game.
+        (
+            "games",
+            IndexModel::builder()
+                .keys(doc! { "creator": 1, "name_normalized": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .partial_filter_expression(doc! { "reissue": false })
+                        .build(),
+                )
+                .build(),
+        ),
+        // Incremental sync via updated_after
+        (
+            "games",
+            IndexModel::builder().keys(doc! { "updated_at": 1 }).build(),
+        ),
+        // Per-game download counts and time-ranged analytics
+        (
+            "downloads",
+            IndexModel::builder().keys(doc! { "game_id": 1 }).build(),
+        ),
+        (
+            "downloads",
+            IndexModel::builder().keys(doc! { "ts": 1 }).build(),
+        ),
+        // Chronological per-game price charts
+        (
+            "price_history",
+            IndexModel::builder()
+                .keys(doc! { "game_id": 1, "ts": 1 })
+                .build(),
+        ),
+        // One review per reviewer per game; also serves per-game review listings
+        (
+            "reviews",
+            IndexModel::builder()
+                .keys(doc! { "game_id": 1, "reviewer": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "reviews",
+            IndexModel::builder()
+                .keys(doc! { "game_id": 1, "created_at": -1 })
+                .build(),
+        ),
+        // One report per reporter per game; also serves per-game report listings
+        (
+            "reports",
+            IndexModel::builder()
+                .keys(doc! { "game_id": 1, "reporter": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "reports",
+            IndexModel::builder()
+                .keys(doc! { "created_at": -1 })
+                .build(),
+        ),
+    ]
+}
+
+// Create every index independently so one failure (e.g. a conflicting text
+// index) can't stop the rest from being built. Fails if a unique index is
+// missing afterwards; other failures are only logged.
+pub async fn ensure_indexes(db: &Database) -> Result<(), mongodb::error::Error> {
+    backfill_normalized_names(db).await?;
+
+    let mut missing_unique = None;
+    for (collection, index) in indexes() {
+        let unique = index
+            .options
+            .as_ref()
+            .and_then(|options| options.unique)
+            .unwrap_or(false);
+        let keys = index.keys.clone();
+        if let Err(e) = db
+            .collection::<Document>(collection)
+            .create_index(index)
+            .await
+        {
+            eprintln!(
+                "[BACKEND] Failed to create {}index {} on {}: {}",
+                if unique { "unique " } else { "" },
+                keys,
+                collection,
+                e
+            );
+            if unique {
+                missing_unique.get_or_insert(e);
+            }
+        }
+    }
+
+    missing_unique.map_or(Ok(()), Err)
+}
+
+// Names whose normalized form may be stale: leading, trailing, repeated or
+// non-space whitespace, or control characters
+const UNCLEAN_NAME: &str = r"^\s|\s$|\s\s|[^\S ]|\p{Cc}";
+
+// Games created before name_normalized existed, or before it collapsed
+// whitespace, get the same key create_game checks against. Runs before the
+// unique index on it is built.
+async fn backfill_normalized_names(db: &Database) -> Result<(), mongodb::error::Error> {
+    let games = db.collection::<Document>("games");
+    let mut stale = games
+        .find(doc! {
+            "$or": [
+                { "name_normalized": { "$exists": false } },
+                { "name": { "$regex": UNCLEAN_NAME } },
+            ]
+        })
+        .projection(doc! { "name": 1, "name_normalized": 1 })
+        .await?;
+
+    while let Some(game) = stale.next().await {
+        let game = game?;
+        let (Ok(id), Ok(name)) = (game.get_object_id("_id"), game.get_str("name")) else {
+            continue;
+        };
+        let normalized = normalized_name(name);
+        if game.get_str("name_normalized").ok() != Some(normalized.as_str()) {
+            games
+                .update_one(
+                    doc! { "_id": id },
+                    doc! { "$set": { "name_normalized": normalized } },
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+// Single document in `meta` holding the catalog-wide change counter
+const CATALOG_META_ID: &str = "catalog";
+
+// Bump the catalog revision after any game write, returning the new value
+pub async fn bump_catalog_revision(db: &Database) -> Result<i64, mongodb::error::Error> {
+    let meta = db
+        .collection::<Document>("meta")
+        .find_one_and_update(
+            doc! { "_id": CATALOG_META_ID },
+            doc! { "$inc": { "catalog_revision": 1_i64 } },
+        )
+        .upsert(true)
+        .return_document(ReturnDocument::After)
+        .await?;
+
+    Ok(meta
+        .and_then(|meta| meta.get_i64("catalog_revision").ok())
+        .unwrap_or(0))
+}
+
+pub async fn catalog_revision(db: &Database) -> Result<i64, mongodb::error::Error> {
+    let meta = db
+        .collection::<Document>("meta")
+        .find_one(doc! { "_id": CATALOG_META_ID })
+        .await?;
+
+    Ok(meta
+        .and_then(|meta| meta.get_i64("catalog_revision").ok())
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unindexed_test_db;
+
+    fn unique_keys() -> Vec<(&'static str, Document)> {
+        indexes()
+            .into_iter()
+            .filter(|(_, index)| index.options.as_ref().and_then(|o| o.unique) == Some(true))
+            .map(|(collection, index)| (collection, index.keys))
+            .collect()
+    }
+
+    #[test]
+    fn one_per_wallet_rules_have_unique_indexes() {
+        assert_eq!(
+            unique_keys(),
+            [
+                ("games", doc! { "creator": 1, "name_normalized": 1 }),
+                ("reviews", doc! { "game_id": 1, "reviewer": 1 }),
+                ("reports", doc! { "game_id": 1, "reporter": 1 }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn a_failing_index_does_not_block_the_others() {
+        let db = unindexed_test_db().await;
+        // A collection only allows one text index, so ours can't be built
+        db.collection::<Document>("games")
+            .create_index(IndexModel::builder().keys(doc! { "name": "text" }).build())
+            .await
+            .unwrap();
+
+        ensure_indexes(&db).await.unwrap();
+        let report_indexes = db
+            .collection::<Document>("reports")
+            .list_index_names()
+            .await
+            .unwrap();
+        assert!(report_indexes.contains(&"game_id_1_reporter_1".to_string()));
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn missing_unique_indexes_fail_setup() {
+        let db = unindexed_test_db().await;
+        let report = doc! { "game_id": 1, "reporter": "wallet" };
+        db.collection::<Document>("reports")
+            .insert_many([report.clone(), report])
+            .await
+            .unwrap();
+
+        assert!(ensure_indexes(&db).await.is_err());
+        // Everything else is still built
+        let review_indexes = db
+            .collection::<Document>("reviews")
+            .list_index_names()
+            .await
+            .unwrap();
+        assert!(review_indexes.contains(&"game_id_1_reviewer_1".to_string()));
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn legacy_names_are_backfilled_like_new_ones() {
+        let db = unindexed_test_db().await;
+        let games = db.collection::<Document>("games");
+        games
+            .insert_many([
+                doc! { "name": "Space  Miner" },
+                doc! { "name": " Star\tTrader ", "name_normalized": " star\ttrader " },
+                doc! { "name": "Kart Rush", "name_normalized": "kart rush" },
+            ])
+            .await
+            .unwrap();
+
+        ensure_indexes(&db).await.unwrap();
+        let mut normalized = Vec::new();
+        let mut cursor = games.find(doc! {}).sort(doc! { "_id": 1 }).await.unwrap();
+        while let Some(game) = cursor.next().await {
+            normalized.push(
+                game.unwrap()
+                    .get_str("name_normalized")
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        assert_eq!(normalized, ["space miner", "star trader", "kart rush"]);
+
+        db.drop().await.unwrap();
+    }
+}
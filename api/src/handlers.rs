@@ -1,34 +1,514 @@
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
     Json,
 };
-use mongodb::{bson::doc, Database};
-use serde::Deserialize;
+use futures_util::{stream, StreamExt};
+use mongodb::{
+    bson::{self, doc, oid::ObjectId, Bson, Document},
+    options::ReturnDocument,
+    Collection, Database,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 use serde_json::json;
 
-use crate::models::{CreateGameRequest, Game};
+use crate::admin::AdminToken;
+use crate::cache::ListingCache;
+use crate::db;
+use crate::redact;
+use crate::rpc::{RpcError, SolanaRpc};
+use crate::state::AppState;
+use crate::webhooks::Webhooks;
+use crate::models::{
+    format_timestamp, is_supported_platform, is_valid_sha256, normalized_name, published_filter,
+    CreateGameRequest, CreateReportRequest, CreateReviewRequest, DownloadEvent,
+    ExecutableChecksum, Game, GameCsvRow, GameExecutable, GameIndexEntry, GameStatus, GameSummary,
+    HelpfulVoteRequest, LaunchKind, PricePoint, RecordDownloadRequest, Report, Review,
+    UpdateGameRequest,
+    DEFAULT_PRICE_CURRENCY, GAME_CSV_HEADER,
+};
+use crate::validation::{
+    extract_cid, is_valid_launch_uri, is_valid_pubkey, is_valid_url, normalize_tags,
+    validate_create_request, validate_report_request, validate_review_request,
+    validate_update_request,
+};
 
 #[derive(Deserialize)]
 pub struct SearchQuery {
     pub q: Option<String>,
-    pub categories: Option<Vec<String>>,
+    // Comma-separated; a game must carry every listed category
+    pub categories: Option<String>,
+    // Comma-separated and matched case-insensitively; a game must carry
+    // every listed tag
+    pub tags: Option<String>,
     pub min_price: Option<i64>,
     pub max_price: Option<i64>,
+    // Use the full-text index instead of a name regex and rank by relevance
+    pub text_search: Option<bool>,
+    // price_asc | price_desc | newest | name (defaults to relevance for text search)
+    pub sort: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    // Only games with a build for the `X-Platform` target triple
+    pub only_playable: Option<bool>,
+    // Only games created or changed after this RFC 3339 timestamp
+    pub updated_after: Option<DateTime<Utc>>,
+    // `summary` for card-sized entries, `full` (default) for whole documents
+    pub fields: Option<String>,
+}
+
+// Split a comma-separated query value (`?tags=co-op,pvp`). `Query` can't
+// deserialize repeated keys into a Vec, so lists are passed this way.
+fn split_list(raw: Option<&str>) -> Vec<String> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Search conditions for `categories` and `tags`, applied independently: a
+// game must carry every listed category and every listed tag
+fn taxonomy_filters(categories: Option<&str>, tags: Option<&str>) -> Vec<Document> {
+    let mut filters = Vec::new();
+
+    let categories = split_list(categories);
+    if !categories.is_empty() {
+        filters.push(doc! { "categories": { "$all": categories } });
+    }
+
+    let tags = normalize_tags(&split_list(tags));
+    if !tags.is_empty() {
+        filters.push(doc! { "tags": { "$all": tags } });
+    }
+
+    filters
+}
+
+// Page size used when only `page` is given, and the largest allowed `limit`
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const MAX_PAGE_SIZE: u64 = 100;
+
+// Sort document for a listing. `_id` is always appended as the final key so
+// documents with equal sort values keep a stable order across skip-based pages.
+fn build_sort(sort: Option<&str>, text_search: bool) -> Result<Document, String> {
+    let mut sort_doc = match sort {
+        Some("price_asc") => doc! { "price_lamports": 1, "name": 1 },
+        Some("price_desc") => doc! { "price_lamports": -1, "name": 1 },
+        Some("newest") => doc! { "created_at": -1 },
+        Some("name") => doc! { "name": 1 },
+        Some(other) => return Err(format!("Unsupported sort: {}", other)),
+        None if text_search => doc! { "score": { "$meta": "textScore" } },
+        None => doc! {},
+    };
+    sort_doc.insert("_id", 1);
+    Ok(sort_doc)
+}
+
+// (skip, limit) for the requested page, or None when paging wasn't requested
+fn pagination(page: Option<u64>, limit: Option<u64>) -> Option<(u64, i64)> {
+    if page.is_none() && limit.is_none() {
+        return None;
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let page = page.unwrap_or(1).max(1);
+    Some(((page - 1) * limit, limit as i64))
+}
+
+// Record that the catalog changed. A failed bump only delays clients noticing
+// the change, so it's logged rather than failing the write.
+async fn note_catalog_change(db: &Database) {
+    if let Err(e) = db::bump_catalog_revision(db).await {
+        eprintln!("[BACKEND] Failed to bump catalog revision: {}", e);
+    }
+}
+
+// Append to the game's price history. Callers only record a price the game
+// didn't have before the write that set it. Like the catalog revision, a
+// failure is logged, not returned.
+async fn record_price(db: &Database, game_id: ObjectId, price_lamports: i64) {
+    let history = db.collection::<PricePoint>("price_history");
+    let point = PricePoint {
+        _id: None,
+        game_id,
+        price_lamports,
+        ts: Utc::now(),
+    };
+    if let Err(e) = history.insert_one(point).await {
+        eprintln!("[BACKEND] Failed to record price for {}: {}", game_id, e);
+    }
+}
+
+const HIGHLIGHT_MARKER: &str = "**";
+
+// Wrap every case-insensitive occurrence of `query` in `name` with markers,
+// or None when the name doesn't contain it
+fn highlight_matches(name: &str, query: &str) -> Option<String> {
+    let name_chars: Vec<char> = name.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+    if query_chars.is_empty() || query_chars.len() > name_chars.len() {
+        return None;
+    }
+
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    let mut highlighted = String::with_capacity(name.len() + HIGHLIGHT_MARKER.len() * 2);
+    let mut found = false;
+    let mut i = 0;
+
+    while i < name_chars.len() {
+        let is_match = i + query_chars.len() <= name_chars.len()
+            && query_chars
+                .iter()
+                .zip(&name_chars[i..])
+                .all(|(q, n)| same(*q, *n));
+
+        if is_match {
+            found = true;
+            highlighted.push_str(HIGHLIGHT_MARKER);
+            highlighted.extend(&name_chars[i..i + query_chars.len()]);
+            highlighted.push_str(HIGHLIGHT_MARKER);
+            i += query_chars.len();
+        } else {
+            highlighted.push(name_chars[i]);
+            i += 1;
+        }
+    }
+
+    found.then_some(highlighted)
+}
+
+// Append X-Total-Count, plus X-Page and rel=next/prev Link headers when the
+// request was paginated. Links keep the request's other query parameters.
+fn apply_pagination_headers(
+    response: &mut Response,
+    uri: &Uri,
+    paging: Option<(u64, i64)>,
+    total: u64,
+) {
+    let headers = response.headers_mut();
+    headers.insert(HeaderName::from_static("x-total-count"), HeaderValue::from(total));
+
+    let Some((skip, limit)) = paging else {
+        return;
+    };
+    let limit = limit as u64;
+    let page = skip / limit + 1;
+    headers.insert(HeaderName::from_static("x-page"), HeaderValue::from(page));
+
+    let base_query: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && key != "page" && key != "limit"
+        })
+        .collect();
+    let page_url = |page: u64| {
+        let mut query = base_query.clone();
+        let paging = format!("page={}&limit={}", page, limit);
+        query.push(&paging);
+        format!("<{}?{}>", uri.path(), query.join("&"))
+    };
+
+    let mut links = Vec::new();
+    if page > 1 {
+        links.push(format!("{}; rel=\"prev\"", page_url(page - 1)));
+    }
+    if skip + limit < total {
+        links.push(format!("{}; rel=\"next\"", page_url(page + 1)));
+    }
+    if let Ok(link) = HeaderValue::from_str(&links.join(", ")) {
+        if !links.is_empty() {
+            headers.insert(header::LINK, link);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    pub only_playable: Option<bool>,
+    pub updated_after: Option<DateTime<Utc>>,
+    // `csv` for a spreadsheet export; same as `Accept: text/csv`
+    pub format: Option<String>,
+    // `summary` for card-sized entries, `full` (default) for whole documents
+    pub fields: Option<String>,
+}
+
+// Heavy fields listing cards never show, left out by `fields=summary`
+const SUMMARY_EXCLUDED_FIELDS: [&str; 3] = ["executables", "metadata_uri", "description"];
+
+// Whether `fields` asks for summaries
+fn wants_summary(fields: Option<&str>) -> Result<bool, String> {
+    match fields.map(str::trim) {
+        None | Some("") | Some("full") => Ok(false),
+        Some("summary") => Ok(true),
+        Some(other) => Err(format!(
+            "Invalid fields value: {}. Use summary or full",
+            other
+        )),
+    }
+}
+
+fn summary_projection() -> Document {
+    SUMMARY_EXCLUDED_FIELDS
+        .iter()
+        .map(|field| (field.to_string(), Bson::Int32(0)))
+        .collect()
+}
+
+// Summaries and full documents of the same listing must not share an ETag
+fn summary_etag(etag: &str) -> String {
+    format!("{}-summary\"", etag.trim_end_matches('"'))
+}
+
+async fn collect_summaries(
+    mut cursor: mongodb::Cursor<GameSummary>,
+    highlight_query: &str,
+) -> Vec<GameSummary> {
+    let mut games = Vec::new();
+    while let Some(result) = cursor.next().await {
+        match result.map(GameSummary::migrate) {
+            Ok(mut game) => {
+                game.highlight = highlight_matches(&game.name, highlight_query);
+                games.push(game);
+            }
+            Err(e) => eprintln!("[BACKEND] Failed to deserialize game summary: {}", e),
+        }
+    }
+    games
+}
+
+// CSV when asked for via `?format=csv` or an Accept header listing text/csv;
+// JSON otherwise
+fn wants_csv(headers: &HeaderMap, format: Option<&str>) -> bool {
+    if let Some(format) = format {
+        return format.eq_ignore_ascii_case("csv");
+    }
+    headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| {
+            accept
+                .split(',')
+                .any(|media| media.split(';').next().unwrap_or_default().trim() == "text/csv")
+        })
+}
+
+fn csv_line<T: Serialize>(row: T) -> Result<Bytes, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer.serialize(row).map_err(|e| e.to_string())?;
+    writer.into_inner().map(Bytes::from).map_err(|e| e.to_string())
+}
+
+// Stream the listing as CSV, header row first
+fn csv_response(cursor: mongodb::Cursor<Game>) -> Response {
+    let header_row = csv_line(GAME_CSV_HEADER).unwrap_or_default();
+    let rows = cursor.filter_map(|game| async move {
+        match game {
+            Ok(game) => match csv_line(GameCsvRow::from(&game.migrate())) {
+                Ok(line) => Some(Ok::<_, mongodb::error::Error>(line)),
+                Err(e) => {
+                    eprintln!("[BACKEND] Failed to serialize game as CSV: {}", e);
+                    None
+                }
+            },
+            // Abort the stream on cursor errors so clients see a truncated body
+            Err(e) => Some(Err(e)),
+        }
+    });
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/csv; charset=utf-8")],
+        Body::from_stream(stream::once(async { Ok(header_row) }).chain(rows)),
+    )
+        .into_response()
+}
+
+// Games written after `after`; updated_at is stored in the fixed
+// format_timestamp format, so the string comparison is chronological
+fn updated_after_filter(after: &DateTime<Utc>) -> Document {
+    doc! { "updated_at": { "$gt": format_timestamp(after) } }
+}
+
+// Header carrying the caller's target triple for `only_playable` listings
+const PLATFORM_HEADER: &str = "x-platform";
+
+// Filter matching games with an executable for the caller's platform. Fails
+// when `only_playable` is set without a supported `X-Platform`.
+fn playable_filter(
+    headers: &HeaderMap,
+    only_playable: Option<bool>,
+) -> Result<Option<Document>, String> {
+    if !only_playable.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let platform = headers
+        .get(PLATFORM_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .unwrap_or_default();
+    if !is_supported_platform(platform) {
+        return Err(format!(
+            "only_playable requires a supported X-Platform header, got {:?}",
+            platform
+        ));
+    }
+
+    Ok(Some(doc! {
+        "executables": { "$elemMatch": { "platform": platform } }
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct RelatedQuery {
+    pub limit: Option<i64>,
+}
+
+const DEFAULT_RELATED_LIMIT: i64 = 10;
+const MAX_RELATED_LIMIT: i64 = 50;
+
+#[derive(Deserialize)]
+pub struct LatestByCreatorsRequest {
+    pub creators: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BatchGamesRequest {
+    pub ids: Vec<String>,
+}
+
+// Upper bound on ids accepted by a single batch lookup
+const MAX_BATCH_SIZE: usize = 100;
+
+#[derive(Deserialize)]
+pub struct CandyMachinesRequest {
+    pub addresses: Vec<String>,
+}
+
+// Weak ETag for a listing, derived from the match count and the latest write
+async fn listing_etag(
+    collection: &Collection<Game>,
+    cache: &ListingCache,
+    filter: Document,
+) -> Option<String> {
+    let key = filter.to_string();
+    if let Some(etag) = cache.get_etag(&key) {
+        return Some(etag);
+    }
+
+    let count = collection.count_documents(filter.clone()).await.ok()?;
+    let latest = collection
+        .find_one(filter)
+        .sort(doc! { "updated_at": -1, "created_at": -1 })
+        .await
+        .ok()?
+        .map(|game| game.migrate().updated_at.to_rfc3339())
+        .unwrap_or_default();
+
+    let mut hasher = DefaultHasher::new();
+    count.hash(&mut hasher);
+    latest.hash(&mut hasher);
+
+    let etag = format!("W/\"{:x}\"", hasher.finish());
+    cache.put_etag(key, etag.clone());
+    Some(etag)
+}
+
+// Whether the client's If-None-Match header already covers this ETag
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value.split(',').any(|tag| {
+                let tag = tag.trim();
+                tag == "*" || tag.trim_start_matches("W/") == etag.trim_start_matches("W/")
+            })
+        })
+        .unwrap_or(false)
+}
+
+// Dry run of create_game's validation; nothing is written
+pub async fn validate_game(Json(mut payload): Json<CreateGameRequest>) -> impl IntoResponse {
+    match validate_create_request(&mut payload) {
+        Ok(()) => (StatusCode::OK, Json(json!({ "valid": true }))),
+        Err(errors) => (
+            StatusCode::OK,
+            Json(json!({
+                "valid": false,
+                "errors": errors
+            })),
+        ),
+    }
 }
 
 pub async fn create_game(
     State(db): State<Database>,
-    Json(payload): Json<CreateGameRequest>,
+    State(webhooks): State<Webhooks>,
+    Json(mut payload): Json<CreateGameRequest>,
 ) -> impl IntoResponse {
     println!("[BACKEND] Received create game request:");
     println!("[BACKEND] Name: {}", payload.name);
-    println!("[BACKEND] Price lamports: {}", payload.price_lamports);
+    println!("[BACKEND] Creator: {}", redact::address(&payload.creator));
+    println!("[BACKEND] Price lamports: {:?}", payload.price_lamports);
+
+    // Drafts may be incomplete, so their validation errors are only warnings
+    let warnings = match validate_create_request(&mut payload) {
+        Ok(()) => Vec::new(),
+        Err(errors) if payload.status == GameStatus::Draft => errors,
+        Err(errors) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({
+                    "error": "Validation failed",
+                    "errors": errors
+                })),
+            )
+                .into_response();
+        }
+    };
 
     let collection = db.collection::<Game>("games");
     let game: Game = payload.into();
 
+    if !game.reissue {
+        let existing = collection
+            .find_one(doc! {
+                "creator": &game.creator,
+                "name_normalized": &game.name_normalized,
+            })
+            .await;
+        match existing {
+            Ok(None) => {}
+            Ok(Some(_)) => return duplicate_name_response(&game.name),
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": format!("Failed to check game name: {}", e)
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
     println!("[BACKEND] Game struct price_lamports: {}", game.price_lamports);
 
     match collection.insert_one(game.clone()).await {
@@ -37,10 +517,24 @@ pub async fn create_game(
             response_game._id = Some(result.inserted_id.as_object_id().unwrap());
 
             println!("[BACKEND] Game inserted successfully with ID: {:?}", response_game._id);
+            note_catalog_change(&db).await;
+            if let Some(game_id) = response_game._id {
+                record_price(&db, game_id, response_game.price_lamports).await;
+            }
+            if response_game.status == GameStatus::Published {
+                webhooks.notify_webhooks(&response_game);
+            }
             println!("[BACKEND] Returning price_lamports: {}", response_game.price_lamports);
 
-            (StatusCode::CREATED, Json(response_game)).into_response()
+            if warnings.is_empty() {
+                return (StatusCode::CREATED, Json(response_game)).into_response();
+            }
+            let mut body = json!(response_game);
+            body["warnings"] = json!(warnings);
+            (StatusCode::CREATED, Json(body)).into_response()
         }
+        // Lost a race with a concurrent create of the same name
+        Err(e) if is_duplicate_key(&e) => duplicate_name_response(&game.name),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({
@@ -51,15 +545,105 @@ pub async fn create_game(
     }
 }
 
-pub async fn get_all_games(State(db): State<Database>) -> impl IntoResponse {
+fn duplicate_name_response(name: &str) -> Response {
+    (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "error": format!(
+                "Creator already has a game named {:?}; set reissue to publish it again",
+                name
+            )
+        })),
+    )
+        .into_response()
+}
+
+pub async fn get_all_games(
+    State(db): State<Database>,
+    State(cache): State<Arc<ListingCache>>,
+    headers: HeaderMap,
+    uri: Uri,
+    Query(params): Query<ListQuery>,
+) -> impl IntoResponse {
     let collection = db.collection::<Game>("games");
+    let paging = pagination(params.page, params.limit);
+
+    let mut filters = vec![published_filter()];
+    match playable_filter(&headers, params.only_playable) {
+        Ok(Some(playable)) => filters.push(playable),
+        Ok(None) => {}
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+        }
+    }
+    if let Some(after) = &params.updated_after {
+        filters.push(updated_after_filter(after));
+    }
+    let filter = doc! { "$and": filters };
+    let csv = wants_csv(&headers, params.format.as_deref());
+    let summary = match wants_summary(params.fields.as_deref()) {
+        // CSV rows are already flat, so `fields` doesn't apply to them
+        Ok(summary) => summary && !csv,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+        }
+    };
+
+    // The ETag identifies the JSON body, so CSV responses don't carry one
+    let etag = if csv {
+        None
+    } else {
+        listing_etag(&collection, &cache, filter.clone())
+            .await
+            .map(|etag| if summary { summary_etag(&etag) } else { etag })
+    };
+    if let Some(etag) = &etag {
+        if etag_matches(&headers, etag) {
+            return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag.clone())]).into_response();
+        }
+    }
 
-    match collection.find(doc! {}).await {
+    let total = match collection.count_documents(filter.clone()).await {
+        Ok(total) => total,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": format!("Failed to count games: {}", e)
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    let mut find = collection.find(filter).sort(doc! { "_id": 1 });
+    if summary {
+        find = find.projection(summary_projection());
+    }
+    if let Some((skip, limit)) = paging {
+        find = find.skip(skip).limit(limit);
+    }
+
+    match find.await {
+        Ok(cursor) if csv => {
+            let mut response = csv_response(cursor);
+            apply_pagination_headers(&mut response, &uri, paging, total);
+            response
+        }
+        Ok(cursor) if summary => {
+            let games = collect_summaries(cursor.with_type(), "").await;
+            let mut response = (StatusCode::OK, Json(games)).into_response();
+            apply_pagination_headers(&mut response, &uri, paging, total);
+            if let Some(etag) = etag.and_then(|etag| etag.parse().ok()) {
+                response.headers_mut().insert(header::ETAG, etag);
+            }
+            response
+        }
         Ok(mut cursor) => {
             let mut games = Vec::new();
 
             while let Ok(true) = cursor.advance().await {
-                match cursor.deserialize_current() {
+                match cursor.deserialize_current().map(Game::migrate) {
                     Ok(game) => {
                         println!("[BACKEND] Deserialized game: {} with price: {}", game.name, game.price_lamports);
                         games.push(game);
@@ -71,7 +655,12 @@ pub async fn get_all_games(State(db): State<Database>) -> impl IntoResponse {
             }
 
             println!("[BACKEND] Returning {} games", games.len());
-            (StatusCode::OK, Json(games)).into_response()
+            let mut response = (StatusCode::OK, Json(games)).into_response();
+            apply_pagination_headers(&mut response, &uri, paging, total);
+            if let Some(etag) = etag.and_then(|etag| etag.parse().ok()) {
+                response.headers_mut().insert(header::ETAG, etag);
+            }
+            response
         }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -85,34 +674,57 @@ pub async fn get_all_games(State(db): State<Database>) -> impl IntoResponse {
 
 pub async fn search_games(
     State(db): State<Database>,
+    State(cache): State<Arc<ListingCache>>,
+    headers: HeaderMap,
+    uri: Uri,
     Query(params): Query<SearchQuery>,
 ) -> impl IntoResponse {
     let collection = db.collection::<Game>("games");
 
     // Build filter document with $and operator
-    let mut filters = Vec::new();
+    let mut filters = vec![published_filter()];
+
+    match playable_filter(&headers, params.only_playable) {
+        Ok(Some(playable)) => filters.push(playable),
+        Ok(None) => {}
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+        }
+    }
+
+    let text_search = params.text_search.unwrap_or(false);
+    let mut has_text_filter = false;
+    let paging = pagination(params.page, params.limit);
+    let highlight_query = params.q.as_deref().map(str::trim).unwrap_or_default().to_string();
 
     // Add name filter if provided
     if let Some(q) = params.q {
         if !q.is_empty() {
-            filters.push(doc! {
-                "name": {
-                    "$regex": q,
-                    "$options": "i"
-                }
-            });
+            if text_search {
+                has_text_filter = true;
+                filters.push(doc! {
+                    "$text": {
+                        "$search": q
+                    }
+                });
+            } else {
+                filters.push(doc! {
+                    "name": {
+                        "$regex": q,
+                        "$options": "i"
+                    }
+                });
+            }
         }
     }
 
-    // Add categories filter if provided (game must have ALL selected categories)
-    if let Some(categories) = params.categories {
-        if !categories.is_empty() {
-            filters.push(doc! {
-                "categories": {
-                    "$all": categories
-                }
-            });
-        }
+    filters.extend(taxonomy_filters(
+        params.categories.as_deref(),
+        params.tags.as_deref(),
+    ));
+
+    if let Some(after) = &params.updated_after {
+        filters.push(updated_after_filter(after));
     }
 
     // Add price range filter
@@ -129,26 +741,90 @@ pub async fn search_games(
         });
     }
 
-    // Combine all filters with $and, or use empty filter if no filters provided
-    let final_filter = if filters.is_empty() {
-        doc! {}
-    } else {
-        doc! {
-            "$and": filters
+    // Combine all filters with $and
+    let final_filter = doc! {
+        "$and": filters
+    };
+
+    let summary = match wants_summary(params.fields.as_deref()) {
+        Ok(summary) => summary,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+        }
+    };
+
+    let etag = listing_etag(&collection, &cache, final_filter.clone())
+        .await
+        .map(|etag| if summary { summary_etag(&etag) } else { etag });
+    if let Some(etag) = &etag {
+        if etag_matches(&headers, etag) {
+            return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag.clone())]).into_response();
+        }
+    }
+
+    let sort = match build_sort(params.sort.as_deref(), has_text_filter) {
+        Ok(sort) => sort,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+        }
+    };
+
+    // Counted with the same filter as the page, so clients can spot the result
+    // set changing between pages and restart pagination
+    let total = match collection.count_documents(final_filter.clone()).await {
+        Ok(total) => total,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": format!("Failed to count games: {}", e)
+                })),
+            )
+                .into_response()
         }
     };
 
-    match collection.find(final_filter).await {
+    let mut find = collection.find(final_filter).sort(sort);
+
+    // Project the relevance score only when a text query is involved
+    let mut projection = if summary { summary_projection() } else { doc! {} };
+    if has_text_filter {
+        projection.insert("score", doc! { "$meta": "textScore" });
+    }
+    if !projection.is_empty() {
+        find = find.projection(projection);
+    }
+
+    if let Some((skip, limit)) = paging {
+        find = find.skip(skip).limit(limit);
+    }
+
+    match find.await {
+        Ok(cursor) if summary => {
+            let games = collect_summaries(cursor.with_type(), &highlight_query).await;
+            let mut response = (StatusCode::OK, Json(games)).into_response();
+            apply_pagination_headers(&mut response, &uri, paging, total);
+            if let Some(etag) = etag.and_then(|etag| etag.parse().ok()) {
+                response.headers_mut().insert(header::ETAG, etag);
+            }
+            response
+        }
         Ok(mut cursor) => {
             let mut games = Vec::new();
 
             while let Ok(true) = cursor.advance().await {
-                if let Ok(game) = cursor.deserialize_current() {
+                if let Ok(mut game) = cursor.deserialize_current().map(Game::migrate) {
+                    game.highlight = highlight_matches(&game.name, &highlight_query);
                     games.push(game);
                 }
             }
 
-            (StatusCode::OK, Json(games)).into_response()
+            let mut response = (StatusCode::OK, Json(games)).into_response();
+            apply_pagination_headers(&mut response, &uri, paging, total);
+            if let Some(etag) = etag.and_then(|etag| etag.parse().ok()) {
+                response.headers_mut().insert(header::ETAG, etag);
+            }
+            response
         }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -159,3 +835,3409 @@ pub async fn search_games(
             .into_response(),
     }
 }
+
+pub async fn get_games_batch(
+    State(db): State<Database>,
+    Json(payload): Json<BatchGamesRequest>,
+) -> impl IntoResponse {
+    if payload.ids.len() > MAX_BATCH_SIZE {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("Batch size exceeds maximum of {} ids", MAX_BATCH_SIZE)
+            })),
+        )
+            .into_response();
+    }
+
+    let collection = db.collection::<Game>("games");
+
+    // Ids that aren't valid ObjectIds can never match, so they're reported as missing
+    let object_ids: Vec<ObjectId> = payload
+        .ids
+        .iter()
+        .filter_map(|id| ObjectId::parse_str(id).ok())
+        .collect();
+
+    match collection
+        .find(doc! { "_id": { "$in": object_ids } })
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut found: HashMap<String, Game> = HashMap::new();
+
+            while let Ok(true) = cursor.advance().await {
+                if let Ok(game) = cursor.deserialize_current().map(Game::migrate) {
+                    if let Some(id) = game._id {
+                        found.insert(id.to_hex(), game);
+                    }
+                }
+            }
+
+            // Preserve the order the ids were requested in
+            let mut games = Vec::new();
+            let mut missing = Vec::new();
+            for id in payload.ids {
+                match found.get(&id) {
+                    Some(game) => games.push(game.clone()),
+                    None => missing.push(id),
+                }
+            }
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "games": games,
+                    "missing": missing
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch games: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+// Library building: the games behind the candy machines found in a wallet.
+// Like the id batch, drafts and flagged games are included since the wallet
+// already owns them.
+pub async fn get_games_by_candy_machines(
+    State(db): State<Database>,
+    Json(payload): Json<CandyMachinesRequest>,
+) -> impl IntoResponse {
+    if payload.addresses.len() > MAX_BATCH_SIZE {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("Batch size exceeds maximum of {} addresses", MAX_BATCH_SIZE)
+            })),
+        )
+            .into_response();
+    }
+
+    let collection = db.collection::<Game>("games");
+
+    match collection
+        .find(doc! { "candy_machine_address": { "$in": &payload.addresses } })
+        .sort(doc! { "_id": 1 })
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut found: HashMap<String, Game> = HashMap::new();
+
+            while let Ok(true) = cursor.advance().await {
+                if let Ok(game) = cursor.deserialize_current().map(Game::migrate) {
+                    // Addresses should be unique; if not, the oldest game wins
+                    found.entry(game.candy_machine_address.clone()).or_insert(game);
+                }
+            }
+
+            // Preserve the order the addresses were requested in
+            let mut games = Vec::new();
+            let mut missing = Vec::new();
+            for address in payload.addresses {
+                match found.get(&address) {
+                    Some(game) => games.push(game.clone()),
+                    None => missing.push(address),
+                }
+            }
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "games": games,
+                    "missing": missing
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch games: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+pub async fn upsert_executable(
+    State(db): State<Database>,
+    Path((id, platform)): Path<(String, String)>,
+    Json(mut executable): Json<GameExecutable>,
+) -> impl IntoResponse {
+    let bad_request = |message: String| {
+        (StatusCode::BAD_REQUEST, Json(json!({ "error": message }))).into_response()
+    };
+
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return bad_request(format!("Invalid game id: {}", id));
+    };
+
+    if !is_supported_platform(&platform) {
+        return bad_request(format!("Unsupported platform: {}", platform));
+    }
+
+    if executable.platform != platform {
+        return bad_request(format!(
+            "Executable platform {} does not match path platform {}",
+            executable.platform, platform
+        ));
+    }
+
+    let url_ok = match executable.launch_kind {
+        LaunchKind::Native => is_valid_url(&executable.url),
+        LaunchKind::Uri => is_valid_launch_uri(&executable.url),
+    };
+    if !url_ok {
+        return bad_request(format!("Invalid executable url: {}", executable.url));
+    }
+
+    if let Some(checksum) = &executable.sha256 {
+        if !is_valid_sha256(checksum) {
+            return bad_request("sha256 must be 64 hex characters".to_string());
+        }
+        executable.sha256 = Some(checksum.to_lowercase());
+    }
+
+    let executable_doc = match bson::to_bson(&executable) {
+        Ok(value) => value,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": format!("Failed to encode executable: {}", e)
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    let collection = db.collection::<Game>("games");
+
+    // Replace the existing entry for this platform in place, if there is one
+    let replaced = collection
+        .find_one_and_update(
+            doc! { "_id": object_id, "executables.platform": &platform },
+            doc! {
+                "$set": {
+                    "executables.$": executable_doc.clone(),
+                    "updated_at": format_timestamp(&Utc::now()),
+                },
+                "$inc": { "version": 1 },
+            },
+        )
+        .return_document(ReturnDocument::After)
+        .await;
+
+    // Otherwise append it, guarded so a concurrent append can't add a duplicate
+    let result = match replaced {
+        Ok(None) => {
+            collection
+                .find_one_and_update(
+                    doc! { "_id": object_id, "executables.platform": { "$ne": &platform } },
+                    doc! {
+                        "$push": { "executables": executable_doc },
+                        "$set": { "updated_at": format_timestamp(&Utc::now()) },
+                        "$inc": { "version": 1 },
+                    },
+                )
+                .return_document(ReturnDocument::After)
+                .await
+        }
+        other => other,
+    };
+
+    match result {
+        Ok(Some(game)) => {
+            note_catalog_change(&db).await;
+            (StatusCode::OK, Json(game.migrate().executables)).into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Game not found"
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to update executable: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+pub async fn get_gateways(State(state): State<AppState>) -> impl IntoResponse {
+    (StatusCode::OK, Json(json!({ "gateways": *state.gateways }))).into_response()
+}
+
+// Probe the Solana RPC with `getHealth`. 503 while the circuit breaker is
+// open, so monitoring sees an outage without hammering the RPC.
+pub async fn get_rpc_health(State(rpc): State<SolanaRpc>) -> impl IntoResponse {
+    match rpc.call("getHealth", json!([])).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(json!({ "status": "ok", "circuit": rpc.circuit_state() })),
+        )
+            .into_response(),
+        Err(e) => rpc_error_response(e, &rpc),
+    }
+}
+
+fn rpc_error_response(error: RpcError, rpc: &SolanaRpc) -> Response {
+    match error {
+        RpcError::NotConfigured => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "error": "Solana RPC is not configured" })),
+        )
+            .into_response(),
+        RpcError::CircuitOpen(retry_after) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(json!({
+                "error": "Solana RPC temporarily unavailable",
+                "circuit": rpc.circuit_state(),
+            })),
+        )
+            .into_response(),
+        RpcError::Failed(e) => (
+            StatusCode::BAD_GATEWAY,
+            Json(json!({ "error": format!("Solana RPC request failed: {}", e) })),
+        )
+            .into_response(),
+    }
+}
+
+// Launchers compare their own version against min_client_version on startup
+pub async fn get_version(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(json!({
+            "api_version": env!("CARGO_PKG_VERSION"),
+            "min_client_version": *state.min_client_version,
+        })),
+    )
+        .into_response()
+}
+
+// Stream every listed game as newline-delimited JSON straight from the
+// cursor; drafts and flagged games stay out like in the other public listings
+pub async fn export_games(State(db): State<Database>) -> impl IntoResponse {
+    let collection = db.collection::<Game>("games");
+
+    match collection.find(published_filter()).sort(doc! { "_id": 1 }).await {
+        Ok(cursor) => {
+            let lines = cursor.filter_map(|game| async move {
+                match game {
+                    Ok(game) => match serde_json::to_vec(&game.migrate()) {
+                        Ok(mut line) => {
+                            line.push(b'\n');
+                            Some(Ok::<_, mongodb::error::Error>(Bytes::from(line)))
+                        }
+                        Err(e) => {
+                            eprintln!("[BACKEND] Failed to serialize game for export: {}", e);
+                            None
+                        }
+                    },
+                    // Abort the stream on cursor errors so clients see a truncated body
+                    Err(e) => Some(Err(e)),
+                }
+            });
+
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/x-ndjson")],
+                Body::from_stream(lines),
+            )
+                .into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to export games: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+pub async fn get_checksums(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    let collection = db.collection::<Game>("games");
+
+    match collection.find_one(doc! { "_id": object_id }).await {
+        Ok(Some(game)) => {
+            let checksums: Vec<ExecutableChecksum> = game
+                .migrate()
+                .executables
+                .iter()
+                .map(ExecutableChecksum::from)
+                .collect();
+            (StatusCode::OK, Json(checksums)).into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Game not found"
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch checksums: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+// Games sharing the most categories/tags with the target, falling back to
+// the same creator's other games when nothing overlaps
+pub async fn get_related_games(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Query(params): Query<RelatedQuery>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_RELATED_LIMIT)
+        .clamp(1, MAX_RELATED_LIMIT);
+    let collection = db.collection::<Game>("games");
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch related games: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    let target = match collection.find_one(doc! { "_id": object_id }).await {
+        Ok(Some(game)) => game.migrate(),
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Game not found"
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => return internal_error(e),
+    };
+
+    let overlap = |field: &str, values: &Vec<String>| {
+        doc! {
+            "$size": {
+                "$setIntersection": [{ "$ifNull": [format!("${}", field), []] }, values]
+            }
+        }
+    };
+    let pipeline = vec![
+        doc! {
+            "$match": {
+                "$and": [published_filter(), { "_id": { "$ne": object_id } }]
+            }
+        },
+        doc! {
+            "$addFields": {
+                "overlap": {
+                    "$add": [
+                        overlap("categories", &target.categories),
+                        overlap("tags", &target.tags),
+                    ]
+                }
+            }
+        },
+        doc! { "$match": { "overlap": { "$gt": 0 } } },
+        doc! { "$sort": { "overlap": -1, "created_at": -1, "_id": 1 } },
+        doc! { "$limit": limit },
+        doc! { "$unset": "overlap" },
+    ];
+
+    let mut related = Vec::new();
+    match collection.aggregate(pipeline).await {
+        Ok(mut cursor) => {
+            while let Some(result) = cursor.next().await {
+                match result.and_then(|document| {
+                    bson::from_document::<Game>(document).map_err(Into::into)
+                }) {
+                    Ok(game) => related.push(game.migrate()),
+                    Err(e) => eprintln!("[BACKEND] Skipping related game: {}", e),
+                }
+            }
+        }
+        Err(e) => return internal_error(e),
+    }
+
+    if related.is_empty() {
+        println!(
+            "[BACKEND] No overlapping games for {}, falling back to creator {}",
+            id,
+            redact::address(&target.creator)
+        );
+        let same_creator = collection
+            .find(doc! {
+                "$and": [
+                    published_filter(),
+                    { "creator": &target.creator, "_id": { "$ne": object_id } },
+                ]
+            })
+            .sort(doc! { "created_at": -1, "_id": 1 })
+            .limit(limit);
+
+        match same_creator.await {
+            Ok(mut cursor) => {
+                while let Some(result) = cursor.next().await {
+                    match result {
+                        Ok(game) => related.push(game.migrate()),
+                        Err(e) => eprintln!("[BACKEND] Skipping related game: {}", e),
+                    }
+                }
+            }
+            Err(e) => return internal_error(e),
+        }
+    }
+
+    (StatusCode::OK, Json(related)).into_response()
+}
+
+// Filter matching a game at a specific version. Documents written before
+// versioning have no field, which counts as version 0.
+fn version_filter(object_id: ObjectId, version: i64) -> Document {
+    if version == 0 {
+        doc! {
+            "_id": object_id,
+            "$or": [{ "version": 0 }, { "version": { "$exists": false } }],
+        }
+    } else {
+        doc! { "_id": object_id, "version": version }
+    }
+}
+
+// Partial update guarded by optimistic concurrency: the write only applies if
+// nobody else has updated the game since the client read `expected_version`
+pub async fn update_game(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Json(mut payload): Json<UpdateGameRequest>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    if let Err(errors) = validate_update_request(&mut payload) {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "Validation failed",
+                "errors": errors
+            })),
+        )
+            .into_response();
+    }
+
+    let collection = db.collection::<Game>("games");
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to update game: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    // A price in SOL can't be applied to a game still priced in a token
+    let sol_price_only = payload.price_sol.is_some() && payload.price_currency.is_none();
+    let mut filter = version_filter(object_id, payload.expected_version);
+    if sol_price_only {
+        filter.insert("price_currency", doc! { "$in": [DEFAULT_PRICE_CURRENCY, Bson::Null] });
+    }
+
+    let mut set = payload.to_set_document();
+    set.insert("updated_at", format_timestamp(&Utc::now()));
+
+    // Every write to a game bumps its version, so if the versioned update
+    // below applies, this is exactly the price it replaced
+    let sets_price = set.contains_key("price_lamports");
+    let previous_price = if sets_price {
+        match collection.find_one(filter.clone()).await {
+            Ok(current) => current.map(|game| game.price_lamports),
+            Err(e) => return internal_error(e),
+        }
+    } else {
+        None
+    };
+
+    let result = collection
+        .find_one_and_update(
+            filter,
+            doc! {
+                "$set": set,
+                "$inc": { "version": 1 },
+            },
+        )
+        .return_document(ReturnDocument::After)
+        .await;
+
+    match result {
+        Ok(Some(game)) => {
+            println!("[BACKEND] Updated game {} to version {}", id, game.version);
+            note_catalog_change(&db).await;
+            if sets_price && previous_price != Some(game.price_lamports) {
+                record_price(&db, object_id, game.price_lamports).await;
+            }
+            (StatusCode::OK, Json(game.migrate())).into_response()
+        }
+        // Either the game is gone, the version moved on or the price is in a
+        // token; tell them apart
+        Ok(None) => match collection.find_one(doc! { "_id": object_id }).await {
+            Ok(Some(current))
+                if sol_price_only
+                    && current.version == payload.expected_version
+                    && current.price_currency != DEFAULT_PRICE_CURRENCY =>
+            {
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "error": "Validation failed",
+                        "errors": [{
+                            "field": "price_sol",
+                            "message": "Only valid for SOL prices"
+                        }]
+                    })),
+                )
+                    .into_response()
+            }
+            Ok(Some(current)) => {
+                println!(
+                    "[BACKEND] Stale update for {}: expected version {}, current {}",
+                    id, payload.expected_version, current.version
+                );
+                (
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "Game was modified by someone else",
+                        "current_version": current.version
+                    })),
+                )
+                    .into_response()
+            }
+            Ok(None) => (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Game not found"
+                })),
+            )
+                .into_response(),
+            Err(e) => internal_error(e),
+        },
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn record_download(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Json(payload): Json<RecordDownloadRequest>,
+) -> impl IntoResponse {
+    let bad_request = |message: String| {
+        (StatusCode::BAD_REQUEST, Json(json!({ "error": message }))).into_response()
+    };
+
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return bad_request(format!("Invalid game id: {}", id));
+    };
+    if !is_supported_platform(&payload.platform) {
+        return bad_request(format!("Unsupported platform: {}", payload.platform));
+    }
+    if let Some(wallet) = &payload.wallet {
+        if !is_valid_pubkey(wallet) {
+            return bad_request("wallet must be a valid Solana address".to_string());
+        }
+    }
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to record download: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    match db
+        .collection::<Game>("games")
+        .count_documents(doc! { "_id": object_id })
+        .await
+    {
+        Ok(0) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Game not found"
+                })),
+            )
+                .into_response()
+        }
+        Ok(_) => {}
+        Err(e) => return internal_error(e),
+    }
+
+    let mut event = DownloadEvent {
+        _id: None,
+        game_id: object_id,
+        platform: payload.platform,
+        wallet: payload.wallet,
+        ts: Utc::now(),
+    };
+
+    match db
+        .collection::<DownloadEvent>("downloads")
+        .insert_one(event.clone())
+        .await
+    {
+        Ok(result) => {
+            event._id = result.inserted_id.as_object_id();
+            // Denormalized popularity; the events stay the source of truth.
+            // The count is part of the game document, so the edit time moves
+            // with it for listing ETags and updated_after syncs.
+            if let Err(e) = db
+                .collection::<Game>("games")
+                .update_one(
+                    doc! { "_id": object_id },
+                    doc! {
+                        "$inc": { "download_count": 1_i64 },
+                        "$set": { "updated_at": format_timestamp(&Utc::now()) },
+                    },
+                )
+                .await
+            {
+                eprintln!("[BACKEND] Failed to bump download count: {}", e);
+            }
+            (StatusCode::CREATED, Json(event)).into_response()
+        }
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn get_download_count(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    match db
+        .collection::<DownloadEvent>("downloads")
+        .count_documents(doc! { "game_id": object_id })
+        .await
+    {
+        Ok(count) => (StatusCode::OK, Json(json!({ "count": count }))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to count downloads: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+// Newest game for each creator, in request order; creators with no games get
+// `"game": null`
+pub async fn get_latest_by_creators(
+    State(db): State<Database>,
+    Json(payload): Json<LatestByCreatorsRequest>,
+) -> impl IntoResponse {
+    if payload.creators.len() > MAX_BATCH_SIZE {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("Batch size exceeds maximum of {} creators", MAX_BATCH_SIZE)
+            })),
+        )
+            .into_response();
+    }
+
+    let pipeline = vec![
+        doc! {
+            "$match": {
+                "$and": [published_filter(), { "creator": { "$in": &payload.creators } }]
+            }
+        },
+        doc! { "$sort": { "created_at": -1, "_id": -1 } },
+        doc! { "$group": { "_id": "$creator", "game": { "$first": "$$ROOT" } } },
+    ];
+
+    let collection = db.collection::<Game>("games");
+
+    match collection.aggregate(pipeline).await {
+        Ok(mut cursor) => {
+            let mut latest: HashMap<String, Game> = HashMap::new();
+
+            while let Some(result) = cursor.next().await {
+                let entry = result.map_err(|e| e.to_string()).and_then(|group| {
+                    let creator = group.get_str("_id").map_err(|e| e.to_string())?;
+                    let game = group.get_document("game").map_err(|e| e.to_string())?;
+                    let game =
+                        bson::from_document::<Game>(game.clone()).map_err(|e| e.to_string())?;
+                    Ok((creator.to_string(), game.migrate()))
+                });
+                match entry {
+                    Ok((creator, game)) => {
+                        latest.insert(creator, game);
+                    }
+                    Err(e) => eprintln!("[BACKEND] Skipping latest game entry: {}", e),
+                }
+            }
+
+            let mut seen = HashSet::new();
+            let entries: Vec<serde_json::Value> = payload
+                .creators
+                .iter()
+                .filter(|creator| seen.insert(creator.as_str()))
+                .map(|creator| json!({ "creator": creator, "game": latest.remove(creator) }))
+                .collect();
+
+            (StatusCode::OK, Json(entries)).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch latest games: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+pub async fn get_catalog_revision(State(db): State<Database>) -> impl IntoResponse {
+    match db::catalog_revision(&db).await {
+        Ok(revision) => (StatusCode::OK, Json(json!({ "revision": revision }))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch catalog revision: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+// Clients may reuse the index without revalidating for this long
+const GAME_INDEX_MAX_AGE_SECS: u64 = 300;
+
+// Compact index of every published game for offline browsing. The ETag is
+// the catalog revision, so it only changes when some game does.
+pub async fn get_game_index(
+    State(db): State<Database>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to build game index: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    // Read before the games so a concurrent change can only make the ETag
+    // older than the body, never newer
+    let revision = match db::catalog_revision(&db).await {
+        Ok(revision) => revision,
+        Err(e) => return internal_error(e),
+    };
+    let etag = format!("\"index-{}\"", revision);
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (
+            header::CACHE_CONTROL,
+            format!("public, max-age={}", GAME_INDEX_MAX_AGE_SECS),
+        ),
+        (
+            HeaderName::from_static("x-catalog-revision"),
+            revision.to_string(),
+        ),
+    ];
+    if etag_matches(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    let pipeline = vec![
+        doc! { "$match": published_filter() },
+        doc! { "$sort": { "_id": 1 } },
+        doc! {
+            "$project": {
+                "_id": 0,
+                "id": { "$toString": "$_id" },
+                "name": 1,
+                "image_url": 1,
+                "price_lamports": 1,
+                "categories": 1,
+                "platforms": { "$ifNull": ["$executables.platform", []] },
+            }
+        },
+    ];
+
+    let mut index = Vec::new();
+    match db.collection::<Game>("games").aggregate(pipeline).await {
+        Ok(mut cursor) => {
+            while let Some(result) = cursor.next().await {
+                match result.and_then(|document| {
+                    bson::from_document::<GameIndexEntry>(document).map_err(Into::into)
+                }) {
+                    Ok(entry) => index.push(entry),
+                    Err(e) => eprintln!("[BACKEND] Skipping game in index: {}", e),
+                }
+            }
+        }
+        Err(e) => return internal_error(e),
+    }
+
+    (StatusCode::OK, cache_headers, Json(index)).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct DraftsQuery {
+    pub creator: String,
+}
+
+// A creator's unpublished games, newest first
+pub async fn get_drafts(
+    State(db): State<Database>,
+    Query(params): Query<DraftsQuery>,
+) -> impl IntoResponse {
+    let collection = db.collection::<Game>("games");
+
+    match collection
+        .find(doc! { "creator": &params.creator, "status": "draft" })
+        .sort(doc! { "created_at": -1, "_id": 1 })
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut drafts = Vec::new();
+            while let Some(result) = cursor.next().await {
+                match result {
+                    Ok(game) => drafts.push(game.migrate()),
+                    Err(e) => eprintln!("[BACKEND] Skipping draft: {}", e),
+                }
+            }
+            (StatusCode::OK, Json(drafts)).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch drafts: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+// Make a draft live. Unlike draft creation, validation failures block this.
+pub async fn publish_game(
+    State(db): State<Database>,
+    State(webhooks): State<Webhooks>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    let collection = db.collection::<Game>("games");
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to publish game: {}", e)
+            })),
+        )
+            .into_response()
+    };
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Game not found"
+            })),
+        )
+            .into_response()
+    };
+
+    let game = match collection.find_one(doc! { "_id": object_id }).await {
+        Ok(Some(game)) => game.migrate(),
+        Ok(None) => return not_found(),
+        Err(e) => return internal_error(e),
+    };
+
+    if game.status == GameStatus::Published {
+        return (StatusCode::OK, Json(game)).into_response();
+    }
+
+    let mut request = CreateGameRequest::from(&game);
+    if let Err(errors) = validate_create_request(&mut request) {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "Validation failed",
+                "errors": errors
+            })),
+        )
+            .into_response();
+    }
+
+    // Only flip drafts, so a concurrent publish doesn't bump the version twice.
+    // The normalized values validation produced are stored along with it.
+    let result = collection
+        .find_one_and_update(
+            doc! { "_id": object_id, "status": "draft" },
+            doc! {
+                "$set": {
+                    "status": "published",
+                    "name": &request.name,
+                    "name_normalized": normalized_name(&request.name),
+                    "tags": &request.tags,
+                    "price_currency": &request.price_currency,
+                    "updated_at": format_timestamp(&Utc::now()),
+                },
+                "$inc": { "version": 1 },
+            },
+        )
+        .return_document(ReturnDocument::After)
+        .await;
+
+    match result {
+        Ok(Some(game)) => {
+            println!("[BACKEND] Published game {}", id);
+            note_catalog_change(&db).await;
+            let game = game.migrate();
+            webhooks.notify_webhooks(&game);
+            (StatusCode::OK, Json(game)).into_response()
+        }
+        // Published concurrently; return the current state
+        Ok(None) => match collection.find_one(doc! { "_id": object_id }).await {
+            Ok(Some(game)) => (StatusCode::OK, Json(game.migrate())).into_response(),
+            Ok(None) => not_found(),
+            Err(e) => internal_error(e),
+        },
+        Err(e) => internal_error(e),
+    }
+}
+
+// Duplicate key errors from a unique index (MongoDB error code 11000)
+fn is_duplicate_key(e: &mongodb::error::Error) -> bool {
+    matches!(
+        e.kind.as_ref(),
+        mongodb::error::ErrorKind::Write(mongodb::error::WriteFailure::WriteError(write_error))
+            if write_error.code == 11000
+    )
+}
+
+pub async fn create_review(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Json(mut payload): Json<CreateReviewRequest>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    if let Err(errors) = validate_review_request(&mut payload) {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "Validation failed",
+                "errors": errors
+            })),
+        )
+            .into_response();
+    }
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to save review: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    match db
+        .collection::<Game>("games")
+        .count_documents(doc! { "_id": object_id })
+        .await
+    {
+        Ok(0) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Game not found"
+                })),
+            )
+                .into_response()
+        }
+        Ok(_) => {}
+        Err(e) => return internal_error(e),
+    }
+
+    let mut review = Review {
+        _id: None,
+        game_id: object_id,
+        reviewer: payload.reviewer,
+        rating: payload.rating,
+        comment: payload.comment,
+        created_at: Utc::now(),
+        helpful_count: 0,
+    };
+
+    match db
+        .collection::<Review>("reviews")
+        .insert_one(review.clone())
+        .await
+    {
+        Ok(result) => {
+            review._id = result.inserted_id.as_object_id();
+            println!(
+                "[BACKEND] Review of {} by {}",
+                id,
+                redact::address(&review.reviewer)
+            );
+            (StatusCode::CREATED, Json(review)).into_response()
+        }
+        Err(e) if is_duplicate_key(&e) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "This wallet has already reviewed the game"
+            })),
+        )
+            .into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReviewsQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    // newest | highest | lowest | helpful_desc
+    pub sort: Option<String>,
+    pub min_rating: Option<i32>,
+}
+
+fn build_review_sort(sort: Option<&str>) -> Result<Document, String> {
+    let sort_doc = match sort {
+        None | Some("newest") => doc! { "created_at": -1, "_id": -1 },
+        Some("highest") => doc! { "rating": -1, "created_at": -1, "_id": -1 },
+        Some("lowest") => doc! { "rating": 1, "created_at": -1, "_id": -1 },
+        Some("helpful_desc") => doc! { "helpful_count": -1, "created_at": -1, "_id": -1 },
+        Some(other) => return Err(format!("Unsupported sort: {}", other)),
+    };
+    Ok(sort_doc)
+}
+
+pub async fn get_reviews(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Query(params): Query<ReviewsQuery>,
+) -> impl IntoResponse {
+    let bad_request = |message: String| {
+        (StatusCode::BAD_REQUEST, Json(json!({ "error": message }))).into_response()
+    };
+
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return bad_request(format!("Invalid game id: {}", id));
+    };
+    let sort = match build_review_sort(params.sort.as_deref()) {
+        Ok(sort) => sort,
+        Err(e) => return bad_request(e),
+    };
+
+    let mut filter = doc! { "game_id": object_id };
+    if let Some(min_rating) = params.min_rating {
+        filter.insert("rating", doc! { "$gte": min_rating });
+    }
+
+    // Reviews are always paged; a popular game can have thousands
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch reviews: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    let collection = db.collection::<Review>("reviews");
+    let total = match collection.count_documents(filter.clone()).await {
+        Ok(total) => total,
+        Err(e) => return internal_error(e),
+    };
+
+    match collection
+        .find(filter)
+        .sort(sort)
+        .skip((page - 1) * limit)
+        .limit(limit as i64)
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut reviews = Vec::new();
+            while let Some(result) = cursor.next().await {
+                match result {
+                    Ok(review) => reviews.push(review),
+                    Err(e) => eprintln!("[BACKEND] Skipping review: {}", e),
+                }
+            }
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "reviews": reviews,
+                    "total": total,
+                    "page": page,
+                    "limit": limit,
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => internal_error(e),
+    }
+}
+
+// Every price the game has been listed at, oldest first, for price charts
+pub async fn get_price_history(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch price history: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    match db
+        .collection::<Game>("games")
+        .count_documents(doc! { "_id": object_id })
+        .await
+    {
+        Ok(0) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Game not found"
+                })),
+            )
+                .into_response()
+        }
+        Ok(_) => {}
+        Err(e) => return internal_error(e),
+    }
+
+    match db
+        .collection::<PricePoint>("price_history")
+        .find(doc! { "game_id": object_id })
+        .sort(doc! { "ts": 1, "_id": 1 })
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut history = Vec::new();
+            while let Some(result) = cursor.next().await {
+                match result {
+                    Ok(point) => history.push(point),
+                    Err(e) => eprintln!("[BACKEND] Skipping price point: {}", e),
+                }
+            }
+            (StatusCode::OK, Json(history)).into_response()
+        }
+        Err(e) => internal_error(e),
+    }
+}
+
+// Count a wallet's "helpful" vote once; repeat votes are rejected
+pub async fn mark_review_helpful(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Json(payload): Json<HelpfulVoteRequest>,
+) -> impl IntoResponse {
+    let bad_request = |message: String| {
+        (StatusCode::BAD_REQUEST, Json(json!({ "error": message }))).into_response()
+    };
+
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return bad_request(format!("Invalid review id: {}", id));
+    };
+    if !is_valid_pubkey(&payload.voter) {
+        return bad_request("voter must be a valid Solana address".to_string());
+    }
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to record vote: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    let collection = db.collection::<Review>("reviews");
+
+    // Matching only reviews the voter hasn't voted on keeps the count and the
+    // voter set in step, even for concurrent votes
+    let result = collection
+        .find_one_and_update(
+            doc! { "_id": object_id, "helpful_voters": { "$ne": &payload.voter } },
+            doc! {
+                "$addToSet": { "helpful_voters": &payload.voter },
+                "$inc": { "helpful_count": 1_i64 },
+            },
+        )
+        .return_document(ReturnDocument::After)
+        .await;
+
+    match result {
+        Ok(Some(review)) => {
+            println!(
+                "[BACKEND] Review {} marked helpful by {}",
+                id,
+                redact::address(&payload.voter)
+            );
+            (StatusCode::OK, Json(review)).into_response()
+        }
+        Ok(None) => match collection.count_documents(doc! { "_id": object_id }).await {
+            Ok(0) => (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Review not found"
+                })),
+            )
+                .into_response(),
+            Ok(_) => (
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "This wallet already marked the review helpful"
+                })),
+            )
+                .into_response(),
+            Err(e) => internal_error(e),
+        },
+        Err(e) => internal_error(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DownloadRedirectQuery {
+    pub platform: String,
+}
+
+// Redirect to the platform's build for plain download links. A redirect can't
+// fall back across gateways, so IPFS builds always go to the primary one.
+pub async fn redirect_download(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<DownloadRedirectQuery>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    let not_found = |message: String| {
+        (StatusCode::NOT_FOUND, Json(json!({ "error": message }))).into_response()
+    };
+
+    let game = match state
+        .db
+        .collection::<Game>("games")
+        .find_one(doc! { "_id": object_id })
+        .await
+    {
+        Ok(Some(game)) => game.migrate(),
+        Ok(None) => return not_found("Game not found".to_string()),
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": format!("Failed to fetch game: {}", e)
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    let Some(executable) = game
+        .executables
+        .iter()
+        .find(|executable| executable.platform == params.platform)
+        .filter(|executable| executable.launch_kind == LaunchKind::Native)
+    else {
+        return not_found(format!("No download for platform: {}", params.platform));
+    };
+
+    let location = match (extract_cid(&executable.url), state.gateways.first()) {
+        (Some(cid), Some(gateway)) => format!("{}/ipfs/{}", gateway, cid),
+        // Plain http(s) builds, or no gateways configured for an ipfs:// URL
+        (None, _) if executable.url.starts_with("http") => executable.url.clone(),
+        _ => return not_found("No gateway configured for IPFS downloads".to_string()),
+    };
+
+    match HeaderValue::from_str(&location) {
+        Ok(location) => (StatusCode::FOUND, [(header::LOCATION, location)]).into_response(),
+        Err(_) => not_found(format!("Invalid download URL: {}", executable.url)),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SuggestQuery {
+    pub q: Option<String>,
+    pub limit: Option<i64>,
+}
+
+const DEFAULT_SUGGEST_LIMIT: i64 = 5;
+const MAX_SUGGEST_LIMIT: i64 = 20;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub _id: ObjectId,
+    pub name: String,
+}
+
+// Escape regex metacharacters so user input only matches literally
+fn escape_regex(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Type-ahead names: an anchored prefix match on the lowercased name (so the
+// index applies), most downloaded first, projecting only what's shown
+pub async fn suggest_games(
+    State(db): State<Database>,
+    Query(params): Query<SuggestQuery>,
+) -> impl IntoResponse {
+    let prefix = normalized_name(params.q.as_deref().unwrap_or_default().trim());
+    if prefix.is_empty() {
+        return (StatusCode::OK, Json(Vec::<Suggestion>::new())).into_response();
+    }
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_SUGGEST_LIMIT)
+        .clamp(1, MAX_SUGGEST_LIMIT);
+
+    let filter = doc! {
+        "$and": [
+            published_filter(),
+            { "name_normalized": { "$regex": format!("^{}", escape_regex(&prefix)) } },
+        ]
+    };
+
+    match db
+        .collection::<Suggestion>("games")
+        .find(filter)
+        .projection(doc! { "_id": 1, "name": 1 })
+        .sort(doc! { "download_count": -1, "name_normalized": 1, "_id": 1 })
+        .limit(limit)
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut suggestions = Vec::new();
+            while let Some(result) = cursor.next().await {
+                match result {
+                    Ok(suggestion) => suggestions.push(suggestion),
+                    Err(e) => eprintln!("[BACKEND] Skipping suggestion: {}", e),
+                }
+            }
+            (StatusCode::OK, Json(suggestions)).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch suggestions: {}", e)
+            })),
+        )
+            .into_response(),
+    }
+}
+
+// Reports at which a game is flagged and hidden from public listings
+const REPORT_FLAG_THRESHOLD: i64 = 5;
+
+// Queue a player report for moderators. Each reporter counts once per game;
+// reaching REPORT_FLAG_THRESHOLD flags the game.
+pub async fn report_game(
+    State(db): State<Database>,
+    Path(id): Path<String>,
+    Json(mut payload): Json<CreateReportRequest>,
+) -> impl IntoResponse {
+    let Ok(object_id) = ObjectId::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid game id: {}", id) })),
+        )
+            .into_response();
+    };
+
+    if let Err(errors) = validate_report_request(&mut payload) {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "Validation failed",
+                "errors": errors
+            })),
+        )
+            .into_response();
+    }
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to save report: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    let games = db.collection::<Game>("games");
+    match games.count_documents(doc! { "_id": object_id }).await {
+        Ok(0) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Game not found"
+                })),
+            )
+                .into_response()
+        }
+        Ok(_) => {}
+        Err(e) => return internal_error(e),
+    }
+
+    let mut report = Report {
+        _id: None,
+        game_id: object_id,
+        reporter: payload.reporter,
+        reason: payload.reason,
+        created_at: Utc::now(),
+    };
+
+    match db
+        .collection::<Report>("reports")
+        .insert_one(report.clone())
+        .await
+    {
+        Ok(result) => {
+            report._id = result.inserted_id.as_object_id();
+            println!(
+                "[BACKEND] Report on {} by {}",
+                id,
+                redact::address(&report.reporter)
+            );
+        }
+        Err(e) if is_duplicate_key(&e) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "This wallet has already reported the game"
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => return internal_error(e),
+    }
+
+    let counted = games
+        .find_one_and_update(
+            doc! { "_id": object_id },
+            doc! { "$inc": { "report_count": 1_i64 } },
+        )
+        .return_document(ReturnDocument::After)
+        .await;
+    let game = match counted {
+        Ok(Some(game)) => game,
+        // Deleted since the existence check; the report stays queued
+        Ok(None) => return (StatusCode::CREATED, Json(report)).into_response(),
+        Err(e) => return internal_error(e),
+    };
+
+    if game.report_count >= REPORT_FLAG_THRESHOLD && !game.flagged {
+        let flagged = games
+            .update_one(
+                doc! { "_id": object_id, "flagged": { "$ne": true } },
+                doc! {
+                    "$set": {
+                        "flagged": true,
+                        "updated_at": format_timestamp(&Utc::now()),
+                    }
+                },
+            )
+            .await;
+        match flagged {
+            Ok(result) if result.modified_count > 0 => {
+                println!(
+                    "[BACKEND] Flagged game {} after {} reports",
+                    id, game.report_count
+                );
+                note_catalog_change(&db).await;
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[BACKEND] Failed to flag game {}: {}", id, e),
+        }
+    }
+
+    (StatusCode::CREATED, Json(report)).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct ReportsQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    pub game_id: Option<String>,
+}
+
+// Moderation queue, newest first. Needs `Authorization: Bearer <ADMIN_TOKEN>`.
+pub async fn get_reports(
+    State(db): State<Database>,
+    State(admin_token): State<AdminToken>,
+    headers: HeaderMap,
+    Query(params): Query<ReportsQuery>,
+) -> impl IntoResponse {
+    if !admin_token.authorizes(&headers) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            Json(json!({ "error": "A valid admin token is required" })),
+        )
+            .into_response();
+    }
+
+    let mut filter = doc! {};
+    if let Some(game_id) = &params.game_id {
+        let Ok(object_id) = ObjectId::parse_str(game_id) else {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": format!("Invalid game id: {}", game_id) })),
+            )
+                .into_response();
+        };
+        filter.insert("game_id", object_id);
+    }
+
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let internal_error = |e: mongodb::error::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": format!("Failed to fetch reports: {}", e)
+            })),
+        )
+            .into_response()
+    };
+
+    let collection = db.collection::<Report>("reports");
+    let total = match collection.count_documents(filter.clone()).await {
+        Ok(total) => total,
+        Err(e) => return internal_error(e),
+    };
+
+    match collection
+        .find(filter)
+        .sort(doc! { "created_at": -1, "_id": -1 })
+        .skip((page - 1) * limit)
+        .limit(limit as i64)
+        .await
+    {
+        Ok(mut cursor) => {
+            let mut reports = Vec::new();
+            while let Some(result) = cursor.next().await {
+                match result {
+                    Ok(report) => reports.push(report),
+                    Err(e) => eprintln!("[BACKEND] Skipping report: {}", e),
+                }
+            }
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "reports": reports,
+                    "total": total,
+                    "page": page,
+                    "limit": limit,
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => internal_error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{body_json, game, game_request, test_db, unconnected_db};
+    use serde_json::Value;
+
+    fn search_query(query: &str) -> SearchQuery {
+        let uri: Uri = format!("/games/search?{}", query).parse().unwrap();
+        Query::<SearchQuery>::try_from_uri(&uri).unwrap().0
+    }
+
+    #[test]
+    fn search_lists_are_comma_separated() {
+        let params = search_query("q=mario&categories=platformer,%20racing&tags=Co-op");
+        assert_eq!(
+            split_list(params.categories.as_deref()),
+            ["platformer", "racing"]
+        );
+        assert_eq!(split_list(params.tags.as_deref()), ["Co-op"]);
+        assert!(split_list(Some(" , ,")).is_empty());
+        assert!(split_list(None).is_empty());
+    }
+
+    #[test]
+    fn category_and_tag_filters_combine() {
+        let params = search_query("categories=platformer,racing&tags=Co-op,PVP,co-op");
+        let filters = taxonomy_filters(params.categories.as_deref(), params.tags.as_deref());
+        assert_eq!(
+            filters,
+            vec![
+                doc! { "categories": { "$all": ["platformer", "racing"] } },
+                doc! { "tags": { "$all": ["co-op", "pvp"] } },
+            ]
+        );
+    }
+
+    #[test]
+    fn tag_filter_works_without_categories() {
+        let params = search_query("tags=multiplayer");
+        assert_eq!(
+            taxonomy_filters(params.categories.as_deref(), params.tags.as_deref()),
+            vec![doc! { "tags": { "$all": ["multiplayer"] } }]
+        );
+        assert!(taxonomy_filters(None, Some("")).is_empty());
+    }
+
+    async fn search(db: &Database, query: &str) -> Response {
+        search_with_headers(db, query, HeaderMap::new()).await
+    }
+
+    async fn search_with_headers(db: &Database, query: &str, headers: HeaderMap) -> Response {
+        let uri: Uri = format!("/games/search?{}", query).parse().unwrap();
+        let params = Query::<SearchQuery>::try_from_uri(&uri).unwrap();
+        let cache = Arc::new(ListingCache::default());
+        search_games(State(db.clone()), State(cache), headers, uri, params)
+            .await
+            .into_response()
+    }
+
+    async fn list(db: &Database, query: &str) -> Response {
+        list_with_headers(db, query, HeaderMap::new()).await
+    }
+
+    async fn list_with_headers(db: &Database, query: &str, headers: HeaderMap) -> Response {
+        let uri: Uri = format!("/games?{}", query).parse().unwrap();
+        let params = Query::<ListQuery>::try_from_uri(&uri).unwrap();
+        let cache = Arc::new(ListingCache::default());
+        get_all_games(State(db.clone()), State(cache), headers, uri, params)
+            .await
+            .into_response()
+    }
+
+    // Ids of the inserted games, in order
+    async fn insert_games(db: &Database, games: impl IntoIterator<Item = Game>) -> Vec<ObjectId> {
+        let result = db
+            .collection::<Game>("games")
+            .insert_many(games)
+            .await
+            .unwrap();
+        (0..result.inserted_ids.len())
+            .map(|index| result.inserted_ids[&index].as_object_id().unwrap())
+            .collect()
+    }
+
+    async fn update(db: &Database, id: &str, body: Value) -> Response {
+        let payload: UpdateGameRequest = serde_json::from_value(body).unwrap();
+        update_game(State(db.clone()), Path(id.to_string()), Json(payload))
+            .await
+            .into_response()
+    }
+
+    async fn create(db: &Database, request: CreateGameRequest) -> Response {
+        create_game(
+            State(db.clone()),
+            State(Webhooks::from_env(reqwest::Client::new())),
+            Json(request),
+        )
+        .await
+        .into_response()
+    }
+
+    fn names(body: &Value) -> Vec<&str> {
+        body.as_array()
+            .unwrap()
+            .iter()
+            .map(|game| game["name"].as_str().unwrap())
+            .collect()
+    }
+
+    fn total_count(response: &Response) -> u64 {
+        response.headers()["x-total-count"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn search_total_follows_the_filter() {
+        let db = test_db().await;
+        insert_games(
+            &db,
+            [
+                game("Dungeon One", &["rpg"], &["co-op"]),
+                game("Dungeon Two", &["rpg"], &[]),
+                game("Kart Rush", &["racing"], &["co-op"]),
+            ],
+        )
+        .await;
+
+        let response = search(&db, "categories=rpg&limit=1").await;
+        assert_eq!(total_count(&response), 2);
+        assert_eq!(body_json(response).await.as_array().unwrap().len(), 1);
+        let response = search(&db, "tags=co-op&categories=racing").await;
+        assert_eq!(total_count(&response), 1);
+
+        let response = list(&db, "limit=1").await;
+        assert_eq!(total_count(&response), 3);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn price_history_records_each_new_price() {
+        let db = test_db().await;
+        let request = game_request("Price Watch", &["rpg"], &[]);
+        let response = create_game(
+            State(db.clone()),
+            State(Webhooks::from_env(reqwest::Client::new())),
+            Json(request),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let game = db
+            .collection::<Game>("games")
+            .find_one(doc! { "name": "Price Watch" })
+            .await
+            .unwrap()
+            .unwrap();
+        let id = game._id.unwrap().to_hex();
+
+        // Two price changes, then a write that keeps the price
+        for (version, price) in [(0, 2_000_000), (1, 3_000_000), (2, 3_000_000)] {
+            let body = json!({ "expected_version": version, "price_lamports": price });
+            assert_eq!(update(&db, &id, body).await.status(), StatusCode::OK);
+        }
+
+        let response = get_price_history(State(db.clone()), Path(id))
+            .await
+            .into_response();
+        let history = body_json(response).await;
+        let prices: Vec<i64> = history
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|point| point["price_lamports"].as_i64().unwrap())
+            .collect();
+        assert_eq!(prices, [1_000_000, 2_000_000, 3_000_000]);
+
+        db.drop().await.unwrap();
+    }
+
+    #[test]
+    fn text_search_defaults_to_relevance_order() {
+        assert_eq!(
+            build_sort(None, true).unwrap(),
+            doc! { "score": { "$meta": "textScore" }, "_id": 1 }
+        );
+        assert_eq!(build_sort(None, false).unwrap(), doc! { "_id": 1 });
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn text_search_results_carry_descending_scores() {
+        let db = test_db().await;
+        let mut strong = game("Space Miner", &["sim"], &[]);
+        strong.description = "Mine asteroids in deep space, trade in space stations".to_string();
+        insert_games(
+            &db,
+            [
+                strong,
+                game("Space Farm", &["sim"], &[]),
+                game("Kart Rush", &["racing"], &[]),
+            ],
+        )
+        .await;
+
+        let results = body_json(search(&db, "q=space&text_search=true").await).await;
+        let results = results.as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["name"], "Space Miner");
+        let scores: Vec<f64> = results
+            .iter()
+            .map(|game| {
+                game["score"]
+                    .as_f64()
+                    .expect("score on text search results")
+            })
+            .collect();
+        assert!(
+            scores[0] > scores[1],
+            "scores should be descending: {:?}",
+            scores
+        );
+
+        let results = body_json(search(&db, "q=space").await).await;
+        assert!(results
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|game| game.get("score").is_none()));
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn batch_size_is_capped() {
+        let ids = vec![ObjectId::new().to_hex(); MAX_BATCH_SIZE + 1];
+        let response = get_games_batch(
+            State(unconnected_db().await),
+            Json(BatchGamesRequest { ids }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn batch_keeps_request_order_and_reports_missing_ids() {
+        let db = test_db().await;
+        let ids = insert_games(
+            &db,
+            [game("First", &["rpg"], &[]), game("Second", &["rpg"], &[])],
+        )
+        .await;
+        let absent = ObjectId::new().to_hex();
+        let request = BatchGamesRequest {
+            ids: vec![
+                ids[1].to_hex(),
+                "not-an-id".to_string(),
+                ids[0].to_hex(),
+                absent.clone(),
+            ],
+        };
+
+        let body = body_json(
+            get_games_batch(State(db.clone()), Json(request))
+                .await
+                .into_response(),
+        )
+        .await;
+        let names: Vec<&str> = body["games"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|game| game["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["Second", "First"]);
+        assert_eq!(body["missing"], json!(["not-an-id", absent]));
+
+        db.drop().await.unwrap();
+    }
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn etags_match_weakly_and_in_lists() {
+        let etag = "W/\"abc\"";
+        assert!(etag_matches(&if_none_match("W/\"abc\""), etag));
+        assert!(etag_matches(&if_none_match("\"abc\""), etag));
+        assert!(etag_matches(&if_none_match("W/\"old\", W/\"abc\""), etag));
+        assert!(etag_matches(&if_none_match("*"), etag));
+        assert!(!etag_matches(&if_none_match("W/\"old\""), etag));
+        assert!(!etag_matches(&HeaderMap::new(), etag));
+
+        // Summaries get their own tag so a full listing isn't served from one
+        assert_eq!(summary_etag(etag), "W/\"abc-summary\"");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn unchanged_listing_is_not_modified() {
+        let db = test_db().await;
+        insert_games(&db, [game("First", &["rpg"], &[])]).await;
+
+        let response = list(&db, "").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let response = list_with_headers(&db, "", if_none_match(&etag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+
+        insert_games(&db, [game("Second", &["rpg"], &[])]).await;
+        let response = list_with_headers(&db, "", if_none_match(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag.as_str());
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_reports_every_validation_error_at_once() {
+        let mut request = game_request(" ", &["rpg"], &[]);
+        request.creator = "not a wallet".to_string();
+        request.price_lamports = Some(-1);
+        request.image_url = "cover.png".to_string();
+        request.executables[0].url = "ftp://example.com/game".to_string();
+
+        let response = create_game(
+            State(unconnected_db().await),
+            State(Webhooks::from_env(reqwest::Client::new())),
+            Json(request),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = body_json(response).await;
+        let fields: Vec<&str> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "name",
+                "creator",
+                "price_lamports",
+                "image_url",
+                "executables[0].url"
+            ]
+        );
+    }
+
+    #[test]
+    fn every_sort_ends_with_an_id_tiebreaker() {
+        for sort in [
+            None,
+            Some("price_asc"),
+            Some("price_desc"),
+            Some("newest"),
+            Some("name"),
+        ] {
+            let sort_doc = build_sort(sort, false).unwrap();
+            assert_eq!(
+                sort_doc.keys().last().map(String::as_str),
+                Some("_id"),
+                "{:?}",
+                sort
+            );
+        }
+        assert_eq!(
+            build_sort(Some("price_asc"), false).unwrap(),
+            doc! { "price_lamports": 1, "name": 1, "_id": 1 }
+        );
+        assert!(build_sort(Some("cheapest"), false).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn same_price_pages_have_no_duplicates_or_gaps() {
+        let db = test_db().await;
+        // Same price and name, so only the _id tiebreaker orders them
+        let ids = insert_games(
+            &db,
+            (0..5).map(|_| Game {
+                reissue: true,
+                ..game("Clone", &["rpg"], &[])
+            }),
+        )
+        .await;
+
+        let mut seen = Vec::new();
+        for page in 1..=3 {
+            let query = format!("sort=price_asc&limit=2&page={}", page);
+            let results = body_json(search(&db, &query).await).await;
+            for game in results.as_array().unwrap() {
+                seen.push(game["_id"]["$oid"].as_str().unwrap().to_string());
+            }
+        }
+        let mut expected: Vec<String> = ids.iter().map(|id| id.to_hex()).collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn export_streams_one_game_per_line() {
+        let db = test_db().await;
+        let names = ["First", "Second", "Third"];
+        insert_games(&db, names.map(|name| game(name, &["rpg"], &[]))).await;
+        insert_games(
+            &db,
+            [
+                Game {
+                    status: GameStatus::Draft,
+                    ..game("Draft", &["rpg"], &[])
+                },
+                Game {
+                    flagged: true,
+                    ..game("Flagged", &["rpg"], &[])
+                },
+            ],
+        )
+        .await;
+
+        let response = export_games(State(db.clone())).await.into_response();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+
+        let mut body = Vec::new();
+        let mut stream = response.into_body().into_data_stream();
+        while let Some(chunk) = stream.next().await {
+            body.extend_from_slice(&chunk.unwrap());
+        }
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.ends_with('\n'));
+        let exported: Vec<String> = body
+            .lines()
+            .map(|line| {
+                let game: serde_json::Value = serde_json::from_str(line).unwrap();
+                game["name"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(exported, names);
+
+        db.drop().await.unwrap();
+    }
+
+    #[test]
+    fn highlights_every_occurrence_case_insensitively() {
+        assert_eq!(
+            highlight_matches("Space Miner: Deep SPACE", "space").as_deref(),
+            Some("**Space** Miner: Deep **SPACE**")
+        );
+        // Matches don't overlap
+        assert_eq!(
+            highlight_matches("aaaa", "aa").as_deref(),
+            Some("**aa****aa**")
+        );
+        // Non-ASCII names keep their characters intact
+        assert_eq!(
+            highlight_matches("Café Crème", "CRÈME").as_deref(),
+            Some("Café **Crème**")
+        );
+    }
+
+    #[test]
+    fn no_highlight_without_a_match() {
+        assert_eq!(highlight_matches("Kart Rush", "space"), None);
+        assert_eq!(highlight_matches("Kart Rush", ""), None);
+        assert_eq!(highlight_matches("Kart", "Kart Rush"), None);
+    }
+
+    #[tokio::test]
+    async fn checksums_need_a_valid_id() {
+        let response = get_checksums(State(unconnected_db().await), Path("nope".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn checksums_list_every_executable() {
+        let db = test_db().await;
+        let mut game = game("Space Miner", &["sim"], &[]);
+        game.executables = vec![
+            GameExecutable {
+                sha256: Some("ab".repeat(32)),
+                size_bytes: Some(1024),
+                ..game.executables[0].clone()
+            },
+            GameExecutable {
+                platform: "x86_64-pc-windows-msvc".to_string(),
+                sha256: None,
+                size_bytes: None,
+                ..game.executables[0].clone()
+            },
+        ];
+        let ids = insert_games(&db, [game]).await;
+
+        let response = get_checksums(State(db.clone()), Path(ids[0].to_hex()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_json(response).await,
+            json!([
+                {
+                    "platform": "x86_64-unknown-linux-gnu",
+                    "sha256": "ab".repeat(32),
+                    "size_bytes": 1024,
+                },
+                { "platform": "x86_64-pc-windows-msvc", "sha256": null, "size_bytes": null },
+            ])
+        );
+
+        let response = get_checksums(State(db.clone()), Path(ObjectId::new().to_hex()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        db.drop().await.unwrap();
+    }
+
+    async fn related_names(db: &Database, id: ObjectId, limit: Option<i64>) -> Vec<String> {
+        let response = get_related_games(
+            State(db.clone()),
+            Path(id.to_hex()),
+            Query(RelatedQuery { limit }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        body_json(response)
+            .await
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|game| game["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn related_games_rank_by_overlap() {
+        let db = test_db().await;
+        let draft = Game {
+            status: GameStatus::Draft,
+            ..game("Unreleased Quest", &["rpg", "fantasy"], &["co-op"])
+        };
+        let ids = insert_games(
+            &db,
+            [
+                game("Target", &["rpg", "fantasy"], &["co-op"]),
+                game("Some Overlap", &["rpg"], &[]),
+                game("Most Overlap", &["rpg", "fantasy"], &["co-op"]),
+                game("No Overlap", &["racing"], &[]),
+                draft,
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            related_names(&db, ids[0], None).await,
+            ["Most Overlap", "Some Overlap"]
+        );
+        assert_eq!(related_names(&db, ids[0], Some(1)).await, ["Most Overlap"]);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn related_games_fall_back_to_the_same_creator() {
+        let db = test_db().await;
+        let by_other_creator = |name: &str, categories: &[&str]| Game {
+            creator: "22222222222222222222222222222222".to_string(),
+            ..game(name, categories, &[])
+        };
+        let ids = insert_games(
+            &db,
+            [
+                by_other_creator("Lonely Puzzle", &["puzzle"]),
+                by_other_creator("Sibling Racer", &["racing"]),
+                game("Stranger Shooter", &["shooter"], &[]),
+            ],
+        )
+        .await;
+
+        assert_eq!(related_names(&db, ids[0], None).await, ["Sibling Racer"]);
+
+        db.drop().await.unwrap();
+    }
+
+    #[test]
+    fn version_zero_matches_documents_written_before_versioning() {
+        let id = ObjectId::new();
+        assert_eq!(
+            version_filter(id, 0),
+            doc! { "_id": id, "$or": [{ "version": 0 }, { "version": { "$exists": false } }] }
+        );
+        assert_eq!(version_filter(id, 3), doc! { "_id": id, "version": 3_i64 });
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn stale_updates_conflict_instead_of_clobbering() {
+        let db = test_db().await;
+        let id = insert_games(&db, [game("Space Miner", &["sim"], &[])]).await[0].to_hex();
+
+        let first = update(
+            &db,
+            &id,
+            json!({ "expected_version": 0, "price_lamports": 5 }),
+        )
+        .await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(body_json(first).await["version"], 1);
+
+        // A second editor still holding version 0
+        let stale = update(
+            &db,
+            &id,
+            json!({ "expected_version": 0, "price_lamports": 7 }),
+        )
+        .await;
+        assert_eq!(stale.status(), StatusCode::CONFLICT);
+        assert_eq!(body_json(stale).await["current_version"], 1);
+
+        let stored = db
+            .collection::<Game>("games")
+            .find_one(doc! {})
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((stored.version, stored.price_lamports), (1, 5));
+
+        let missing = update(
+            &db,
+            &ObjectId::new().to_hex(),
+            json!({ "expected_version": 0, "name": "Gone" }),
+        )
+        .await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn latest_by_creators_caps_the_batch() {
+        let creators = vec!["creator".to_string(); MAX_BATCH_SIZE + 1];
+        let response = get_latest_by_creators(
+            State(unconnected_db().await),
+            Json(LatestByCreatorsRequest { creators }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn latest_by_creators_keeps_creator_order() {
+        let db = test_db().await;
+        let by = |creator: &str, name: &str, days_ago: i64| Game {
+            creator: creator.to_string(),
+            created_at: Utc::now() - chrono::Duration::days(days_ago),
+            ..game(name, &["rpg"], &[])
+        };
+        insert_games(
+            &db,
+            [
+                by("alice", "Old Alice", 30),
+                by("alice", "New Alice", 1),
+                by("bob", "Bob Game", 5),
+            ],
+        )
+        .await;
+
+        let creators = ["bob", "carol", "alice", "bob"].map(String::from).to_vec();
+        let response = get_latest_by_creators(
+            State(db.clone()),
+            Json(LatestByCreatorsRequest { creators }),
+        )
+        .await
+        .into_response();
+        let body = body_json(response).await;
+        let entries: Vec<(&str, Option<&str>)> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                (
+                    entry["creator"].as_str().unwrap(),
+                    entry["game"]["name"].as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("bob", Some("Bob Game")),
+                ("carol", None),
+                ("alice", Some("New Alice"))
+            ]
+        );
+
+        db.drop().await.unwrap();
+    }
+
+    // Pagination headers for a request to `uri` over `total` results
+    fn paged_headers(uri: &str, total: u64) -> HeaderMap {
+        let uri: Uri = uri.parse().unwrap();
+        let params = Query::<ListQuery>::try_from_uri(&uri).unwrap();
+        let mut response = StatusCode::OK.into_response();
+        apply_pagination_headers(
+            &mut response,
+            &uri,
+            pagination(params.page, params.limit),
+            total,
+        );
+        response.headers().clone()
+    }
+
+    #[test]
+    fn pagination_defaults_and_clamps() {
+        assert_eq!(pagination(None, None), None);
+        assert_eq!(
+            pagination(Some(3), None),
+            Some((2 * DEFAULT_PAGE_SIZE, DEFAULT_PAGE_SIZE as i64))
+        );
+        assert_eq!(pagination(None, Some(10)), Some((0, 10)));
+        assert_eq!(pagination(Some(0), Some(0)), Some((0, 1)));
+        assert_eq!(
+            pagination(Some(2), Some(1000)),
+            Some((MAX_PAGE_SIZE, MAX_PAGE_SIZE as i64))
+        );
+    }
+
+    #[test]
+    fn unpaged_responses_only_carry_the_total() {
+        let headers = paged_headers("/games", 42);
+        assert_eq!(headers["x-total-count"], "42");
+        assert!(headers.get("x-page").is_none());
+        assert!(headers.get(header::LINK).is_none());
+    }
+
+    #[test]
+    fn links_follow_the_page_boundaries() {
+        let first = paged_headers("/games?page=1&limit=10", 25);
+        assert_eq!(first["x-page"], "1");
+        assert_eq!(
+            first[header::LINK],
+            "</games?page=2&limit=10>; rel=\"next\""
+        );
+
+        let middle = paged_headers("/games?page=2&limit=10", 25);
+        assert_eq!(
+            middle[header::LINK],
+            "</games?page=1&limit=10>; rel=\"prev\", </games?page=3&limit=10>; rel=\"next\""
+        );
+
+        let last = paged_headers("/games?page=3&limit=10", 25);
+        assert_eq!(last[header::LINK], "</games?page=2&limit=10>; rel=\"prev\"");
+
+        // A full last page has no next link either
+        let exact = paged_headers("/games?page=2&limit=10", 20);
+        assert_eq!(
+            exact[header::LINK],
+            "</games?page=1&limit=10>; rel=\"prev\""
+        );
+
+        // A single page has no links at all
+        assert!(paged_headers("/games?limit=10", 3)
+            .get(header::LINK)
+            .is_none());
+    }
+
+    #[test]
+    fn links_keep_the_other_query_parameters() {
+        let headers = paged_headers("/games/search?q=space&limit=5&sort=name&page=1", 12);
+        assert_eq!(headers["x-total-count"], "12");
+        assert_eq!(
+            headers[header::LINK],
+            "</games/search?q=space&sort=name&page=2&limit=5>; rel=\"next\""
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn drafts_stay_hidden_until_published() {
+        let db = test_db().await;
+        insert_games(&db, [game("Live", &["rpg"], &[])]).await;
+
+        let draft = |name: &str| CreateGameRequest {
+            status: GameStatus::Draft,
+            ..game_request(name, &["rpg"], &[])
+        };
+        let response = create(&db, draft("Staged")).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let staged = body_json(response).await;
+        // Incomplete drafts are saved, their validation errors only warnings
+        let response = create(
+            &db,
+            CreateGameRequest {
+                executables: Vec::new(),
+                ..draft("Sketch")
+            },
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            body_json(response).await["warnings"][0]["field"],
+            "executables"
+        );
+
+        assert_eq!(names(&body_json(list(&db, "").await).await), ["Live"]);
+        assert_eq!(
+            names(&body_json(search(&db, "categories=rpg").await).await),
+            ["Live"]
+        );
+
+        let query = DraftsQuery {
+            creator: "11111111111111111111111111111111".to_string(),
+        };
+        let drafts = get_drafts(State(db.clone()), Query(query))
+            .await
+            .into_response();
+        let drafts = body_json(drafts).await;
+        let mut draft_names = names(&drafts);
+        draft_names.sort();
+        assert_eq!(draft_names, ["Sketch", "Staged"]);
+
+        let publish = |id: &str| {
+            publish_game(
+                State(db.clone()),
+                State(Webhooks::from_env(reqwest::Client::new())),
+                Path(id.to_string()),
+            )
+        };
+        let staged_id = staged["_id"]["$oid"].as_str().unwrap();
+        assert_eq!(
+            publish(staged_id).await.into_response().status(),
+            StatusCode::OK
+        );
+        let sketch_id = drafts
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|game| game["name"] == "Sketch")
+            .unwrap()["_id"]["$oid"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let response = publish(&sketch_id).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let listed = body_json(list(&db, "").await).await;
+        let mut listed = names(&listed);
+        listed.sort();
+        assert_eq!(listed, ["Live", "Staged"]);
+
+        db.drop().await.unwrap();
+    }
+
+    fn platform_header(platform: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(PLATFORM_HEADER, platform.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn playable_filter_needs_a_supported_platform() {
+        let linux = platform_header("x86_64-unknown-linux-gnu");
+        assert_eq!(playable_filter(&linux, None), Ok(None));
+        assert_eq!(playable_filter(&linux, Some(false)), Ok(None));
+        assert_eq!(
+            playable_filter(&linux, Some(true)),
+            Ok(Some(doc! {
+                "executables": { "$elemMatch": { "platform": "x86_64-unknown-linux-gnu" } }
+            }))
+        );
+        assert!(playable_filter(&HeaderMap::new(), Some(true)).is_err());
+        assert!(playable_filter(&platform_header("commodore-64"), Some(true)).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn only_playable_keeps_games_with_a_matching_build() {
+        let db = test_db().await;
+        let linux_only = game("Linux Only", &["rpg"], &[]);
+        let windows = GameExecutable {
+            platform: "x86_64-pc-windows-msvc".to_string(),
+            ..linux_only.executables[0].clone()
+        };
+        let windows_only = Game {
+            executables: vec![windows.clone()],
+            ..game("Windows Only", &["rpg"], &[])
+        };
+        let mut both = game("Both", &["rpg"], &[]);
+        both.executables.push(windows);
+        insert_games(&db, [linux_only, windows_only, both]).await;
+
+        let headers = platform_header("x86_64-pc-windows-msvc");
+        let listed =
+            body_json(list_with_headers(&db, "only_playable=true", headers.clone()).await).await;
+        let mut listed = names(&listed);
+        listed.sort();
+        assert_eq!(listed, ["Both", "Windows Only"]);
+
+        let found =
+            body_json(search_with_headers(&db, "q=only&only_playable=true", headers).await).await;
+        assert_eq!(names(&found), ["Windows Only"]);
+
+        let response = list(&db, "only_playable=true").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        db.drop().await.unwrap();
+    }
+
+    #[test]
+    fn updated_after_compares_fixed_width_timestamps() {
+        let after = DateTime::parse_from_rfc3339("2024-03-01T12:00:00+02:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            updated_after_filter(&after),
+            doc! { "updated_at": { "$gt": "2024-03-01T10:00:00.000Z" } }
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn updated_after_returns_only_edited_games() {
+        let db = test_db().await;
+        let last_sync = Utc::now();
+        let stale = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ids = insert_games(
+            &db,
+            ["Space Miner", "Star Trader"].map(|name| Game {
+                created_at: stale,
+                updated_at: stale,
+                ..game(name, &["sim"], &[])
+            }),
+        )
+        .await;
+
+        let edited = update(
+            &db,
+            &ids[1].to_hex(),
+            json!({ "expected_version": 0, "description": "Trade between stars" }),
+        )
+        .await;
+        assert_eq!(edited.status(), StatusCode::OK);
+        let edited = body_json(edited).await;
+        let timestamp = |field: &str| {
+            DateTime::parse_from_rfc3339(edited[field].as_str().unwrap())
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert!(timestamp("updated_at") > last_sync);
+        assert_eq!(timestamp("created_at"), stale);
+
+        let query = format!("updated_after={}", format_timestamp(&last_sync));
+        let listed = body_json(list(&db, &query).await).await;
+        assert_eq!(names(&listed), ["Star Trader"]);
+        let searched = body_json(search(&db, &format!("q=star&{}", query)).await).await;
+        assert_eq!(names(&searched), ["Star Trader"]);
+        let searched = body_json(search(&db, &format!("q=space&{}", query)).await).await;
+        assert!(names(&searched).is_empty());
+
+        db.drop().await.unwrap();
+    }
+
+    async fn insert_reviews(db: &Database, game_id: ObjectId, ratings: &[i32]) -> Vec<ObjectId> {
+        let reviews = ratings.iter().enumerate().map(|(index, &rating)| Review {
+            _id: None,
+            game_id,
+            reviewer: format!("reviewer-{}", index),
+            rating,
+            comment: String::new(),
+            created_at: Utc::now() + chrono::Duration::seconds(index as i64),
+            helpful_count: 0,
+        });
+        let result = db
+            .collection::<Review>("reviews")
+            .insert_many(reviews)
+            .await
+            .unwrap();
+        (0..result.inserted_ids.len())
+            .map(|index| result.inserted_ids[&index].as_object_id().unwrap())
+            .collect()
+    }
+
+    async fn reviews(db: &Database, game_id: ObjectId, query: &str) -> Value {
+        let uri: Uri = format!("/games/{}/reviews?{}", game_id, query)
+            .parse()
+            .unwrap();
+        let params = Query::<ReviewsQuery>::try_from_uri(&uri).unwrap();
+        let response = get_reviews(State(db.clone()), Path(game_id.to_hex()), params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        body_json(response).await
+    }
+
+    fn ratings(body: &Value) -> Vec<i64> {
+        body["reviews"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|review| review["rating"].as_i64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn review_sorts_break_ties_by_recency() {
+        assert_eq!(
+            build_review_sort(None),
+            Ok(doc! { "created_at": -1, "_id": -1 })
+        );
+        assert_eq!(
+            build_review_sort(Some("highest")),
+            Ok(doc! { "rating": -1, "created_at": -1, "_id": -1 })
+        );
+        assert_eq!(
+            build_review_sort(Some("lowest")),
+            Ok(doc! { "rating": 1, "created_at": -1, "_id": -1 })
+        );
+        assert!(build_review_sort(Some("oldest")).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn reviews_sort_by_rating_and_filter_by_min_rating() {
+        let db = test_db().await;
+        let game_id = ObjectId::new();
+        insert_reviews(&db, game_id, &[5, 2, 4, 1, 3]).await;
+        // Another game's reviews never leak in
+        insert_reviews(&db, ObjectId::new(), &[5]).await;
+
+        let newest = reviews(&db, game_id, "").await;
+        assert_eq!(ratings(&newest), [3, 1, 4, 2, 5]);
+        assert_eq!(newest["total"], 5);
+
+        assert_eq!(
+            ratings(&reviews(&db, game_id, "sort=highest").await),
+            [5, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            ratings(&reviews(&db, game_id, "sort=lowest").await),
+            [1, 2, 3, 4, 5]
+        );
+
+        let filtered = reviews(&db, game_id, "sort=lowest&min_rating=3").await;
+        assert_eq!(ratings(&filtered), [3, 4, 5]);
+        assert_eq!(filtered["total"], 3);
+
+        let paged = reviews(&db, game_id, "sort=highest&limit=2&page=2").await;
+        assert_eq!(ratings(&paged), [3, 2]);
+        assert_eq!(paged["total"], 5);
+        assert_eq!(paged["page"], 2);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unknown_review_sorts_are_rejected() {
+        let db = unconnected_db().await;
+        let uri: Uri = "/games/x/reviews?sort=oldest".parse().unwrap();
+        let params = Query::<ReviewsQuery>::try_from_uri(&uri).unwrap();
+        let response = get_reviews(State(db), Path(ObjectId::new().to_hex()), params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    async fn vote_helpful(db: &Database, review_id: ObjectId, voter: &str) -> Response {
+        let payload = HelpfulVoteRequest {
+            voter: voter.to_string(),
+        };
+        mark_review_helpful(State(db.clone()), Path(review_id.to_hex()), Json(payload))
+            .await
+            .into_response()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn helpful_votes_count_each_wallet_once() {
+        let db = test_db().await;
+        let game_id = ObjectId::new();
+        let review_ids = insert_reviews(&db, game_id, &[4, 5]).await;
+        let alice = "1".repeat(32);
+        let bob = "2".repeat(32);
+
+        let first = vote_helpful(&db, review_ids[0], &alice).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        let first = body_json(first).await;
+        assert_eq!(first["helpful_count"], 1);
+        assert!(first.get("helpful_voters").is_none());
+
+        let repeat = vote_helpful(&db, review_ids[0], &alice).await;
+        assert_eq!(repeat.status(), StatusCode::CONFLICT);
+
+        let second = vote_helpful(&db, review_ids[0], &bob).await;
+        assert_eq!(body_json(second).await["helpful_count"], 2);
+
+        // The voted review outranks the newer, higher-rated one
+        let helpful = reviews(&db, game_id, "sort=helpful_desc").await;
+        assert_eq!(ratings(&helpful), [4, 5]);
+
+        let missing = vote_helpful(&db, ObjectId::new(), &alice).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn helpful_votes_need_a_wallet_address() {
+        let db = unconnected_db().await;
+        let response = vote_helpful(&db, ObjectId::new(), "not a wallet").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn create_reports_the_invalid_executable_index() {
+        let db = unconnected_db().await;
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.executables = serde_json::from_value(json!([
+            { "platform": "x86_64-unknown-linux-gnu", "url": "https://example.com/miner" },
+            { "platform": "x86_64-pc-windoze", "url": "miner.exe" },
+        ]))
+        .unwrap();
+
+        let response = create(&db, request).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = body_json(response).await;
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| error["field"].as_str().unwrap())
+                .collect::<Vec<_>>(),
+            ["executables[1].platform", "executables[1].url"]
+        );
+        assert!(errors[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("x86_64-pc-windoze"));
+    }
+
+    async fn suggest(db: &Database, query: &str) -> Vec<String> {
+        let uri: Uri = format!("/games/suggest?{}", query).parse().unwrap();
+        let params = Query::<SuggestQuery>::try_from_uri(&uri).unwrap();
+        let response = suggest_games(State(db.clone()), params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        names(&body_json(response).await)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn regex_metacharacters_are_escaped() {
+        assert_eq!(escape_regex("space miner"), "space miner");
+        assert_eq!(escape_regex("c++"), "c\\+\\+");
+        assert_eq!(
+            escape_regex("a.b*(c)|[d]{e}^$\\"),
+            "a\\.b\\*\\(c\\)\\|\\[d\\]\\{e\\}\\^\\$\\\\"
+        );
+        assert_eq!(escape_regex("what?"), "what\\?");
+    }
+
+    #[tokio::test]
+    async fn empty_suggest_queries_return_nothing() {
+        let db = unconnected_db().await;
+        assert!(suggest(&db, "").await.is_empty());
+        assert!(suggest(&db, "q=%20%20").await.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn suggestions_match_name_prefixes_by_popularity() {
+        let db = test_db().await;
+        insert_games(
+            &db,
+            [
+                ("Space Miner", 10),
+                ("Space Trader", 50),
+                ("Spacewar", 5),
+                ("Deep Space", 100),
+                ("C++ Quest", 1),
+            ]
+            .map(|(name, downloads)| Game {
+                download_count: downloads,
+                ..game(name, &["sim"], &[])
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            suggest(&db, "q=SPACE").await,
+            ["Space Trader", "Space Miner", "Spacewar"]
+        );
+        assert_eq!(
+            suggest(&db, "q=space&limit=2").await,
+            ["Space Trader", "Space Miner"]
+        );
+        assert_eq!(suggest(&db, "q=space%20m").await, ["Space Miner"]);
+        // Metacharacters match literally instead of as a pattern
+        assert_eq!(suggest(&db, "q=c%2B%2B").await, ["C++ Quest"]);
+        assert!(suggest(&db, "q=.").await.is_empty());
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn creators_cannot_reuse_a_game_name() {
+        let db = test_db().await;
+        let first = create(&db, game_request("Space Miner", &["sim"], &[])).await;
+        assert_eq!(first.status(), StatusCode::CREATED);
+
+        // Names compare case-insensitively
+        let duplicate = create(&db, game_request("SPACE MINER", &["sim"], &[])).await;
+        assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+
+        let other_creator = CreateGameRequest {
+            creator: "2".repeat(32),
+            ..game_request("Space Miner", &["sim"], &[])
+        };
+        assert_eq!(
+            create(&db, other_creator).await.status(),
+            StatusCode::CREATED
+        );
+
+        let reissue = CreateGameRequest {
+            reissue: true,
+            ..game_request("Space Miner", &["sim"], &[])
+        };
+        assert_eq!(create(&db, reissue).await.status(), StatusCode::CREATED);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn the_unique_name_index_reports_duplicate_keys() {
+        let db = test_db().await;
+        let games = db.collection::<Game>("games");
+        games
+            .insert_one(game("Space Miner", &["sim"], &[]))
+            .await
+            .unwrap();
+
+        // A create that raced past the find_one check
+        let error = games
+            .insert_one(game("space miner", &["sim"], &[]))
+            .await
+            .unwrap_err();
+        assert!(is_duplicate_key(&error));
+
+        let reissue = Game {
+            reissue: true,
+            ..game("Space Miner", &["sim"], &[])
+        };
+        assert!(games.insert_one(reissue).await.is_ok());
+
+        db.drop().await.unwrap();
+    }
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn csv_is_negotiated_by_format_or_accept() {
+        assert!(!wants_csv(&HeaderMap::new(), None));
+        assert!(wants_csv(&HeaderMap::new(), Some("CSV")));
+        assert!(!wants_csv(&accept("text/csv"), Some("json")));
+        assert!(wants_csv(
+            &accept("application/json;q=0.5, text/csv; q=0.9"),
+            None
+        ));
+        assert!(!wants_csv(&accept("application/json, text/*"), None));
+    }
+
+    #[test]
+    fn csv_rows_flatten_and_quote_fields() {
+        let created_at = DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let game = Game {
+            _id: ObjectId::parse_str("65e1c0ffee0000000000beef").ok(),
+            created_at,
+            updated_at: created_at,
+            ..game("Space Miner, Deluxe", &["sim", "space"], &["co-op"])
+        };
+
+        assert_eq!(
+            csv_line(GAME_CSV_HEADER).unwrap(),
+            "id,name,creator,categories,tags,price_lamports,price_currency,executables,created_at,updated_at\n"
+        );
+        assert_eq!(
+            csv_line(GameCsvRow::from(&game)).unwrap(),
+            format!(
+                "65e1c0ffee0000000000beef,\"Space Miner, Deluxe\",{},sim;space,co-op,1000000,SOL,1,\
+                 2024-03-01T12:00:00.000Z,2024-03-01T12:00:00.000Z\n",
+                "1".repeat(32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn listings_export_as_csv_on_request() {
+        let db = test_db().await;
+        let ids = insert_games(&db, [game("Space Miner", &["sim", "space"], &[])]).await;
+
+        let response = list_with_headers(&db, "", accept("text/csv")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("id,name,creator,categories,"));
+        assert!(lines[1].starts_with(&format!("{},Space Miner,", ids[0].to_hex())));
+        assert!(lines[1].contains(",sim;space,"));
+
+        let response = list(&db, "format=csv").await;
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        // JSON stays the default
+        let response = list(&db, "").await;
+        assert_eq!(names(&body_json(response).await), ["Space Miner"]);
+
+        db.drop().await.unwrap();
+    }
+
+    async fn report(db: &Database, game_id: ObjectId, reporter: &str) -> Response {
+        let payload = CreateReportRequest {
+            reporter: reporter.to_string(),
+            reason: "Bundles malware".to_string(),
+        };
+        report_game(State(db.clone()), Path(game_id.to_hex()), Json(payload))
+            .await
+            .into_response()
+    }
+
+    async fn stored_game(db: &Database, id: ObjectId) -> Game {
+        db.collection::<Game>("games")
+            .find_one(doc! { "_id": id })
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn games_are_flagged_at_the_report_threshold() {
+        let db = test_db().await;
+        let ids = insert_games(
+            &db,
+            [
+                game("Space Miner", &["sim"], &[]),
+                game("Star Trader", &["sim"], &[]),
+            ],
+        )
+        .await;
+        let reporters: Vec<String> = (0..REPORT_FLAG_THRESHOLD)
+            .map(|index| format!("{}{}", "1".repeat(31), index + 2))
+            .collect();
+
+        for reporter in &reporters[..reporters.len() - 1] {
+            assert_eq!(
+                report(&db, ids[0], reporter).await.status(),
+                StatusCode::CREATED
+            );
+        }
+        // Repeat reports neither count nor flag
+        let repeat = report(&db, ids[0], &reporters[0]).await;
+        assert_eq!(repeat.status(), StatusCode::CONFLICT);
+        let below = stored_game(&db, ids[0]).await;
+        assert_eq!(below.report_count, REPORT_FLAG_THRESHOLD - 1);
+        assert!(!below.flagged);
+        assert_eq!(
+            names(&body_json(list(&db, "sort=name").await).await),
+            ["Space Miner", "Star Trader"]
+        );
+
+        let last = report(&db, ids[0], reporters.last().unwrap()).await;
+        assert_eq!(last.status(), StatusCode::CREATED);
+        let flagged = stored_game(&db, ids[0]).await;
+        assert_eq!(flagged.report_count, REPORT_FLAG_THRESHOLD);
+        assert!(flagged.flagged);
+        assert_eq!(
+            names(&body_json(list(&db, "sort=name").await).await),
+            ["Star Trader"]
+        );
+        assert!(names(&body_json(search(&db, "q=space").await).await).is_empty());
+
+        let uri: Uri = format!("/reports?game_id={}", ids[0]).parse().unwrap();
+        let params = Query::<ReportsQuery>::try_from_uri(&uri).unwrap();
+        let token = AdminToken::new(Some("s3cret".to_string()));
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer s3cret".parse().unwrap());
+        let queue = get_reports(State(db.clone()), State(token), headers, params)
+            .await
+            .into_response();
+        let queue = body_json(queue).await;
+        assert_eq!(queue["total"], REPORT_FLAG_THRESHOLD);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_need_a_reporter_and_a_reason() {
+        let db = unconnected_db().await;
+        let payload = CreateReportRequest {
+            reporter: "not a wallet".to_string(),
+            reason: "   ".to_string(),
+        };
+        let response = report_game(State(db), Path(ObjectId::new().to_hex()), Json(payload))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = body_json(response).await;
+        assert_eq!(body["errors"][0]["field"], "reporter");
+        assert_eq!(body["errors"][1]["field"], "reason");
+    }
+
+    #[test]
+    fn summary_fields_are_opt_in() {
+        assert_eq!(wants_summary(None), Ok(false));
+        assert_eq!(wants_summary(Some("full")), Ok(false));
+        assert_eq!(wants_summary(Some(" summary ")), Ok(true));
+        assert!(wants_summary(Some("minimal")).is_err());
+        assert_eq!(
+            summary_projection(),
+            doc! { "executables": 0, "metadata_uri": 0, "description": 0 }
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_fields_values_are_rejected() {
+        let db = unconnected_db().await;
+        assert_eq!(
+            list(&db, "fields=minimal").await.status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            search(&db, "q=space&fields=minimal").await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn summary_listings_leave_out_heavy_fields() {
+        let db = test_db().await;
+        insert_games(&db, [game("Space Miner", &["sim"], &["co-op"])]).await;
+
+        for response in [
+            list(&db, "fields=summary").await,
+            search(&db, "q=space&fields=summary").await,
+        ] {
+            let body = body_json(response).await;
+            let entry = body[0].as_object().unwrap();
+            for field in SUMMARY_EXCLUDED_FIELDS {
+                assert!(!entry.contains_key(field), "{} not trimmed", field);
+            }
+            assert_eq!(entry["name"], "Space Miner");
+            assert_eq!(entry["categories"], json!(["sim"]));
+            assert_eq!(entry["tags"], json!(["co-op"]));
+            assert_eq!(entry["price_lamports"], 1_000_000);
+            assert!(entry.contains_key("image_url") && entry.contains_key("_id"));
+        }
+
+        // Full documents stay the default
+        let body = body_json(list(&db, "").await).await;
+        assert_eq!(body[0]["executables"].as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["description"], "Space Miner description");
+
+        db.drop().await.unwrap();
+    }
+
+    async fn by_candy_machines(db: &Database, addresses: &[&str]) -> Response {
+        let payload = CandyMachinesRequest {
+            addresses: addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect(),
+        };
+        get_games_by_candy_machines(State(db.clone()), Json(payload))
+            .await
+            .into_response()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn candy_machine_lookups_keep_request_order_and_list_missing() {
+        let db = test_db().await;
+        let with_candy_machine = |name: &str, address: &str| Game {
+            candy_machine_address: address.to_string(),
+            ..game(name, &["sim"], &[])
+        };
+        insert_games(
+            &db,
+            [
+                with_candy_machine("Space Miner", "cm-miner"),
+                with_candy_machine("Star Trader", "cm-trader"),
+                Game {
+                    status: GameStatus::Draft,
+                    ..with_candy_machine("Nebula", "cm-nebula")
+                },
+            ],
+        )
+        .await;
+
+        let response =
+            by_candy_machines(&db, &["cm-trader", "cm-unknown", "cm-nebula", "cm-miner"]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        // Owned drafts still show up in the library
+        assert_eq!(
+            names(&body["games"]),
+            ["Star Trader", "Nebula", "Space Miner"]
+        );
+        assert_eq!(body["missing"], json!(["cm-unknown"]));
+
+        let empty = body_json(by_candy_machines(&db, &[]).await).await;
+        assert_eq!(empty, json!({ "games": [], "missing": [] }));
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn candy_machine_batches_are_capped() {
+        let db = unconnected_db().await;
+        let addresses = vec!["cm"; MAX_BATCH_SIZE + 1];
+        let response = by_candy_machines(&db, &addresses).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn game_index_is_compact_and_cacheable() {
+        let db = test_db().await;
+        let mut listed = game("Space Miner", &["sim"], &[]);
+        let mut windows = listed.executables[0].clone();
+        windows.platform = "x86_64-pc-windows-msvc".to_string();
+        listed.executables.push(windows);
+        let ids = insert_games(
+            &db,
+            [
+                listed,
+                Game {
+                    status: GameStatus::Draft,
+                    ..game("Nebula", &["sim"], &[])
+                },
+            ],
+        )
+        .await;
+
+        let response = get_game_index(State(db.clone()), HeaderMap::new())
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            format!("public, max-age={}", GAME_INDEX_MAX_AGE_SECS).as_str()
+        );
+        assert!(response.headers().contains_key("x-catalog-revision"));
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let index = body_json(response).await;
+        assert_eq!(
+            index,
+            json!([{
+                "id": ids[0].to_hex(),
+                "name": "Space Miner",
+                "image_url": "https://example.com/cover.png",
+                "price_lamports": 1_000_000,
+                "categories": ["sim"],
+                "platforms": ["x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc"],
+            }])
+        );
+
+        let cached = get_game_index(State(db.clone()), if_none_match(&etag))
+            .await
+            .into_response();
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+
+        db.drop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn the_report_queue_needs_the_admin_token() {
+        let db = unconnected_db().await;
+        let token = AdminToken::new(Some("s3cret".to_string()));
+        for authorization in [None, Some("Bearer wrong"), Some("s3cret")] {
+            let mut headers = HeaderMap::new();
+            if let Some(authorization) = authorization {
+                headers.insert(header::AUTHORIZATION, authorization.parse().unwrap());
+            }
+            let uri: Uri = "/reports".parse().unwrap();
+            let params = Query::<ReportsQuery>::try_from_uri(&uri).unwrap();
+            let response = get_reports(State(db.clone()), State(token.clone()), headers, params)
+                .await
+                .into_response();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs MongoDB"]
+    async fn downloads_count_as_edits_for_syncing_clients() {
+        let db = test_db().await;
+        let stale = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ids = insert_games(
+            &db,
+            [Game {
+                updated_at: stale,
+                ..game("Space Miner", &["sim"], &[])
+            }],
+        )
+        .await;
+        let before = list_with_headers(&db, "", HeaderMap::new()).await;
+        let etag = before.headers()[header::ETAG].to_str().unwrap().to_string();
+        let last_sync = Utc::now();
+
+        let recorded = record_download(
+            State(db.clone()),
+            Path(ids[0].to_hex()),
+            Json(RecordDownloadRequest {
+                platform: "x86_64-unknown-linux-gnu".to_string(),
+                wallet: None,
+            }),
+        )
+        .await
+        .into_response();
+        assert_eq!(recorded.status(), StatusCode::CREATED);
+
+        let game = stored_game(&db, ids[0]).await;
+        assert_eq!(game.download_count, 1);
+        assert!(game.updated_at > last_sync);
+        let listed = list_with_headers(&db, "", if_none_match(&etag)).await;
+        assert_eq!(listed.status(), StatusCode::OK);
+        let query = format!("updated_after={}", format_timestamp(&last_sync));
+        let synced = body_json(list(&db, &query).await).await;
+        assert_eq!(names(&synced), ["Space Miner"]);
+
+        db.drop().await.unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use crate::config::env_number;
+
+// Defaults for HTTP_CONNECT_TIMEOUT_SECS and HTTP_TIMEOUT_SECS
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+// Limits for every outbound request (webhooks, Solana RPC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    pub connect: Duration,
+    // Whole request, including reading the response body
+    pub request: Duration,
+}
+
+impl Timeouts {
+    pub fn from_env() -> Self {
+        Timeouts {
+            connect: Duration::from_secs(env_number(
+                "HTTP_CONNECT_TIMEOUT_SECS",
+                DEFAULT_CONNECT_TIMEOUT_SECS,
+            )),
+            request: Duration::from_secs(env_number("HTTP_TIMEOUT_SECS", DEFAULT_TIMEOUT_SECS)),
+        }
+    }
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts {
+            connect: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            request: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+        }
+    }
+}
+
+// The one client outbound calls share, so they share its connection pool
+pub fn client(timeouts: Timeouts) -> Result<reqwest::Client, reqwest::Error> {
+    reqwest::Client::builder()
+        .connect_timeout(timeouts.connect)
+        .timeout(timeouts.request)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn requests_give_up_after_the_timeout() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let client = client(Timeouts {
+            connect: Duration::from_secs(1),
+            request: Duration::from_millis(100),
+        })
+        .unwrap();
+        let error = client.get(url).send().await.unwrap_err();
+        assert!(error.is_timeout());
+    }
+}
@@ -1,14 +1,148 @@
+mod admin;
+mod cache;
+mod config;
 mod db;
 mod handlers;
+mod http;
 mod models;
+mod redact;
+mod rpc;
+mod state;
+#[cfg(test)]
+mod test_support;
+mod validation;
+mod webhooks;
 
 use axum::{
-    routing::{get, post},
+    http::{header, HeaderName, HeaderValue},
+    routing::{get, patch, post, put},
     Router,
 };
 use dotenv::dotenv;
 use std::env;
-use tower_http::cors::{Any, CorsLayer};
+use std::sync::Arc;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+// Origins used by the Tauri webview when CORS_ORIGINS isn't set
+const DEFAULT_CORS_ORIGINS: &str = "tauri://localhost,http://localhost:1420";
+
+// Public gateways recommended when IPFS_GATEWAYS isn't set
+const DEFAULT_GATEWAYS: &str = "https://ipfs.io,https://dweb.link,https://gateway.pinata.cloud";
+
+// Launchers older than this are told to update; bump on breaking API changes
+const DEFAULT_MIN_CLIENT_VERSION: &str = "0.1.0";
+
+fn parse_gateways(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|gateway| gateway.trim().trim_end_matches('/'))
+        .filter(|gateway| !gateway.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_origins(raw: &str) -> Vec<HeaderValue> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                eprintln!("Ignoring invalid CORS origin: {}", origin);
+                None
+            }
+        })
+        .collect()
+}
+
+fn cors_layer() -> CorsLayer {
+    let allow_any = env::var("CORS_ALLOW_ANY")
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    let origin = if allow_any {
+        println!("CORS: allowing any origin (CORS_ALLOW_ANY=true)");
+        AllowOrigin::any()
+    } else {
+        let raw = env::var("CORS_ORIGINS").unwrap_or_else(|_| DEFAULT_CORS_ORIGINS.to_string());
+        let origins = parse_origins(&raw);
+        println!("CORS: allowed origins {:?}", origins);
+        AllowOrigin::list(origins)
+    };
+
+    CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(Any)
+        .allow_headers(Any)
+        // Let browser clients read caching and pagination headers
+        .expose_headers([
+            header::ETAG,
+            header::LINK,
+            HeaderName::from_static("x-total-count"),
+            HeaderName::from_static("x-page"),
+            HeaderName::from_static("x-catalog-revision"),
+        ])
+}
+
+fn router(state: state::AppState) -> Router {
+    let router = Router::new()
+        .route("/games", post(handlers::create_game))
+        .route("/games", get(handlers::get_all_games))
+        .route("/games/search", get(handlers::search_games))
+        .route("/games/suggest", get(handlers::suggest_games))
+        .route("/games/index", get(handlers::get_game_index))
+        .route("/games/batch", post(handlers::get_games_batch))
+        .route(
+            "/games/by-candy-machines",
+            post(handlers::get_games_by_candy_machines),
+        )
+        .route("/games/validate", post(handlers::validate_game))
+        .route("/games/drafts", get(handlers::get_drafts))
+        .route(
+            "/games/latest-by-creators",
+            post(handlers::get_latest_by_creators),
+        )
+        .route("/games/export", get(handlers::export_games))
+        .route("/config/gateways", get(handlers::get_gateways))
+        .route("/rpc/health", get(handlers::get_rpc_health))
+        .route("/version", get(handlers::get_version))
+        .route("/catalog/revision", get(handlers::get_catalog_revision))
+        .route(
+            "/games/:id/executables/:platform",
+            put(handlers::upsert_executable),
+        )
+        .route("/games/:id", patch(handlers::update_game))
+        .route("/games/:id/publish", post(handlers::publish_game))
+        .route("/games/:id/checksums", get(handlers::get_checksums))
+        .route("/games/:id/related", get(handlers::get_related_games))
+        .route("/games/:id/price-history", get(handlers::get_price_history))
+        .route("/games/:id/download", post(handlers::record_download))
+        .route("/games/:id/download", get(handlers::redirect_download))
+        .route(
+            "/games/:id/downloads/count",
+            get(handlers::get_download_count),
+        )
+        .route("/games/:id/reviews", post(handlers::create_review))
+        .route("/games/:id/reviews", get(handlers::get_reviews))
+        .route("/reviews/:id/helpful", post(handlers::mark_review_helpful))
+        .route("/games/:id/report", post(handlers::report_game));
+
+    // The moderation queue exposes reporter wallets, so it's only served
+    // behind ADMIN_TOKEN
+    let router = if state.admin_token.is_configured() {
+        router.route("/reports", get(handlers::get_reports))
+    } else {
+        println!("Admin: ADMIN_TOKEN not set, GET /reports is disabled");
+        router
+    };
+
+    router
+        // gzip/brotli when the client sends Accept-Encoding. CORS stays the
+        // outer layer so preflights are answered before compression runs.
+        .layer(CompressionLayer::new())
+        .layer(cors_layer())
+        .with_state(state)
+}
 
 #[tokio::main]
 async fn main() {
@@ -18,17 +152,36 @@ async fn main() {
         .await
         .expect("Failed to connect to MongoDB");
 
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+    // Unique indexes back the one-per-wallet rules, so don't serve without them
+    db::ensure_indexes(&database)
+        .await
+        .expect("Failed to create required indexes");
 
-    let app = Router::new()
-        .route("/games", post(handlers::create_game))
-        .route("/games", get(handlers::get_all_games))
-        .route("/games/search", get(handlers::search_games))
-        .layer(cors)
-        .with_state(database);
+    let cache = Arc::new(cache::ListingCache::default());
+    cache::spawn_invalidation_watcher(database.clone(), cache.clone());
+
+    let gateways = parse_gateways(
+        &env::var("IPFS_GATEWAYS").unwrap_or_else(|_| DEFAULT_GATEWAYS.to_string()),
+    );
+
+    let timeouts = http::Timeouts::from_env();
+    let http = http::client(timeouts).expect("Failed to build HTTP client");
+
+    let state = state::AppState {
+        db: database,
+        http: http.clone(),
+        cache,
+        gateways: Arc::new(gateways),
+        min_client_version: Arc::new(
+            env::var("MIN_CLIENT_VERSION")
+                .unwrap_or_else(|_| DEFAULT_MIN_CLIENT_VERSION.to_string()),
+        ),
+        webhooks: webhooks::Webhooks::from_env(http.clone()),
+        rpc: rpc::SolanaRpc::from_env(http, timeouts),
+        admin_token: admin::AdminToken::from_env(),
+    };
+
+    let app = router(state);
 
     let port = env::var("PORT").unwrap_or_else(|_| "3000".to_string());
     let addr = format!("0.0.0.0:{}", port);
@@ -43,3 +196,98 @@ async fn main() {
         .await
         .expect("Failed to start server");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Serve the full router on an ephemeral port and return its base URL
+    async fn spawn_app() -> String {
+        let gateways = (0..50)
+            .map(|index| format!("https://gateway-{}.example.com", index))
+            .collect();
+        let timeouts = http::Timeouts::default();
+        let http = http::client(timeouts).unwrap();
+        let state = state::AppState {
+            db: test_support::unconnected_db().await,
+            http: http.clone(),
+            cache: Arc::new(cache::ListingCache::default()),
+            gateways: Arc::new(gateways),
+            min_client_version: Arc::new(DEFAULT_MIN_CLIENT_VERSION.to_string()),
+            webhooks: webhooks::Webhooks::from_env(http.clone()),
+            rpc: rpc::SolanaRpc::from_env(http, timeouts),
+            admin_token: admin::AdminToken::default(),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn large_responses_are_compressed_on_request() {
+        let base = spawn_app().await;
+        let client = reqwest::Client::new();
+        let url = format!("{}/config/gateways", base);
+
+        for encoding in ["gzip", "br"] {
+            let response = client
+                .get(&url)
+                .header(header::ACCEPT_ENCODING, encoding)
+                .header(header::ORIGIN, "tauri://localhost")
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+            assert_eq!(response.headers()[header::CONTENT_ENCODING], encoding);
+            // Compression composes with CORS rather than replacing its headers
+            assert_eq!(
+                response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+                "tauri://localhost"
+            );
+        }
+
+        let plain = client.get(&url).send().await.unwrap();
+        assert!(plain.headers().get(header::CONTENT_ENCODING).is_none());
+        let body: serde_json::Value = plain.json().await.unwrap();
+        assert!(body.to_string().contains("gateway-49.example.com"));
+    }
+
+    #[tokio::test]
+    async fn preflights_are_answered_by_cors() {
+        let base = spawn_app().await;
+        let response = reqwest::Client::new()
+            .request(reqwest::Method::OPTIONS, format!("{}/games", base))
+            .header(header::ORIGIN, "tauri://localhost")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "tauri://localhost"
+        );
+    }
+
+    #[tokio::test]
+    async fn version_reports_the_minimum_client_version() {
+        let base = spawn_app().await;
+        let body: serde_json::Value = reqwest::get(format!("{}/version", base))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["api_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(body["min_client_version"], DEFAULT_MIN_CLIENT_VERSION);
+    }
+
+    #[tokio::test]
+    async fn reports_are_not_served_without_an_admin_token() {
+        let base = spawn_app().await;
+        let response = reqwest::get(format!("{}/reports", base)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
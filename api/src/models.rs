@@ -1,28 +1,265 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, SecondsFormat, Utc};
+use mongodb::bson::{Bson, Document};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Target triples the launcher ships builds for
+pub const SUPPORTED_PLATFORMS: &[&str] = &[
+    "x86_64-pc-windows-msvc",
+    "i686-pc-windows-msvc",
+    "x86_64-apple-darwin",
+    "aarch64-apple-darwin",
+    "x86_64-unknown-linux-gnu",
+    "i686-unknown-linux-gnu",
+];
+
+// How the launcher starts an executable
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchKind {
+    // Downloaded binary spawned as a process
+    #[default]
+    Native,
+    // Protocol URI (e.g. steam://run/...) handed to the OS opener
+    Uri,
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GameExecutable {
     pub platform: String, // target triple (e.g., "x86_64-pc-windows-msvc")
-    pub url: String,      // IPFS URL or gateway URL
+    pub url: String,      // IPFS URL or gateway URL, or a protocol URI for `uri` launches
+    #[serde(default)]
+    pub launch_kind: LaunchKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>, // hex-encoded SHA-256 of the executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+// Published hash for one executable, used by the launcher to verify installs
+#[derive(Debug, Serialize, Clone)]
+pub struct ExecutableChecksum {
+    pub platform: String,
+    pub sha256: Option<String>,
+    pub size_bytes: Option<u64>,
+}
+
+impl From<&GameExecutable> for ExecutableChecksum {
+    fn from(executable: &GameExecutable) -> Self {
+        ExecutableChecksum {
+            platform: executable.platform.clone(),
+            sha256: executable.sha256.clone(),
+            size_bytes: executable.size_bytes,
+        }
+    }
+}
+
+pub fn is_supported_platform(platform: &str) -> bool {
+    SUPPORTED_PLATFORMS.contains(&platform)
+}
+
+pub fn is_valid_sha256(checksum: &str) -> bool {
+    checksum.len() == 64 && checksum.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Drafts are only visible to their creator until published
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GameStatus {
+    Draft,
+    // Documents written before drafts existed have no status and are live
+    #[default]
+    Published,
+}
+
+// Filter for public listings: excludes drafts and games flagged by player
+// reports; missing status counts as published
+pub fn published_filter() -> Document {
+    mongodb::bson::doc! { "status": { "$ne": "draft" }, "flagged": { "$ne": true } }
+}
+
+// Native currency; prices in any other currency are SPL token base units
+pub const DEFAULT_PRICE_CURRENCY: &str = "SOL";
+
+fn default_price_currency() -> String {
+    DEFAULT_PRICE_CURRENCY.to_string()
 }
 
+pub const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+// SOL amount to lamports, rounded to the nearest lamport. None for negative,
+// non-finite or out-of-range amounts.
+pub fn sol_to_lamports(sol: f64) -> Option<i64> {
+    let lamports = (sol * LAMPORTS_PER_SOL).round();
+    (lamports.is_finite() && lamports >= 0.0 && lamports <= i64::MAX as f64)
+        .then_some(lamports as i64)
+}
+
+// Duplicate-check and suggestion key: the cleaned-up name, lowercased, so
+// legacy names stored before that cleanup still match new ones
+pub fn normalized_name(name: &str) -> String {
+    crate::validation::clean_name(name).to_lowercase()
+}
+
+// Timestamps filtered with string comparisons (`updated_at`) are written with
+// a fixed millisecond precision so lexicographic order is chronological order
+pub fn format_timestamp(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+mod fixed_timestamp {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(ts: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_timestamp(ts))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        DateTime::<Utc>::deserialize(deserializer)
+    }
+}
+
+// Version written to new documents; older documents are upgraded on read
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Game {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub _id: Option<mongodb::bson::oid::ObjectId>,
+    // Documents written before versioning have no field and read as 0
+    #[serde(default)]
+    pub schema_version: u32,
     pub collection_address: String,
     pub candy_machine_address: String,
     pub name: String,
     pub description: String,
     pub image_url: String,
     pub categories: Vec<String>,
+    // Free-form lowercase labels (multiplayer, controller-support), independent of categories
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Gallery for the game page (CIDs or URLs); image_url stays the cover
+    #[serde(default)]
+    pub screenshots: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trailer_url: Option<String>,
     pub executables: Vec<GameExecutable>,
     pub creator: String,
     pub metadata_uri: String,
+    pub price_lamports: i64, // lamports for SOL, token base units otherwise
+    #[serde(default = "default_price_currency")]
+    pub price_currency: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_mint: Option<String>, // SPL token mint, required unless priced in SOL
+    #[serde(default)]
+    pub status: GameStatus,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    // Set on create and bumped by every write, for incremental sync
+    #[serde(default, with = "fixed_timestamp")]
+    pub updated_at: DateTime<Utc>,
+    // Bumped on every update; writers must send the version they read
+    #[serde(default)]
+    pub version: i64,
+    // Lowercased name backing prefix suggestions
+    #[serde(default)]
+    pub name_normalized: String,
+    // Completed downloads, kept on the game so suggestions can rank by popularity
+    #[serde(default)]
+    pub download_count: i64,
+    // Deliberate re-release under a name the creator already uses; exempt
+    // from the per-creator unique name index
+    #[serde(default)]
+    pub reissue: bool,
+    // Player reports received; at the threshold the game is flagged and
+    // hidden from public listings until moderated
+    #[serde(default)]
+    pub report_count: i64,
+    #[serde(default)]
+    pub flagged: bool,
+    // Text search relevance, only populated on text search results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    // Name with query matches wrapped in `**`, only populated on search results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+// Entry of the offline browsing index, built by an aggregation that keeps
+// only what client-side filtering needs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameIndexEntry {
+    pub id: String,
+    pub name: String,
+    pub image_url: String,
     pub price_lamports: i64,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    // Target triples of the game's executables
+    #[serde(default)]
+    pub platforms: Vec<String>,
+}
+
+// Card-sized listing entry for `fields=summary`, read from a projection that
+// leaves out executables, metadata_uri and description
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _id: Option<mongodb::bson::oid::ObjectId>,
+    pub name: String,
+    pub image_url: String,
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub creator: String,
+    pub price_lamports: i64,
+    #[serde(default = "default_price_currency")]
+    pub price_currency: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_mint: Option<String>,
+    #[serde(default)]
+    pub status: GameStatus,
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "fixed_timestamp")]
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub download_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+impl From<&Game> for GameSummary {
+    fn from(game: &Game) -> Self {
+        GameSummary {
+            _id: game._id,
+            name: game.name.clone(),
+            image_url: game.image_url.clone(),
+            categories: game.categories.clone(),
+            tags: game.tags.clone(),
+            creator: game.creator.clone(),
+            price_lamports: game.price_lamports,
+            price_currency: game.price_currency.clone(),
+            price_mint: game.price_mint.clone(),
+            status: game.status,
+            created_at: game.created_at,
+            updated_at: game.updated_at,
+            download_count: game.download_count,
+            score: game.score,
+            highlight: game.highlight.clone(),
+        }
+    }
+}
+
+impl GameSummary {
+    // Same read-time fixups as Game::migrate for the fields a summary keeps
+    pub fn migrate(mut self) -> Self {
+        if self.updated_at == DateTime::<Utc>::UNIX_EPOCH {
+            self.updated_at = self.created_at;
+        }
+        self
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,27 +270,456 @@ pub struct CreateGameRequest {
     pub description: String,
     pub image_url: String,
     pub categories: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub screenshots: Vec<String>,
+    #[serde(default)]
+    pub trailer_url: Option<String>,
     pub executables: Vec<GameExecutable>,
     pub creator: String,
     pub metadata_uri: String,
-    pub price_lamports: i64,
+    // Either price_lamports or price_sol is required; validation resolves
+    // price_sol into price_lamports
+    #[serde(default)]
+    pub price_lamports: Option<i64>,
+    #[serde(default)]
+    pub price_sol: Option<f64>,
+    #[serde(default = "default_price_currency")]
+    pub price_currency: String,
+    #[serde(default)]
+    pub price_mint: Option<String>,
+    #[serde(default)]
+    pub status: GameStatus,
+    // Opt out of the per-creator unique name check
+    #[serde(default)]
+    pub reissue: bool,
+}
+
+// Partial update; only the fields present are changed. `expected_version`
+// must match the stored version or the update is rejected as stale.
+#[derive(Debug, Deserialize)]
+pub struct UpdateGameRequest {
+    pub expected_version: i64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub categories: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub screenshots: Option<Vec<String>>,
+    pub trailer_url: Option<String>,
+    pub price_lamports: Option<i64>,
+    // Alternative to price_lamports for SOL-priced games
+    pub price_sol: Option<f64>,
+    pub price_currency: Option<String>,
+    pub price_mint: Option<String>,
+}
+
+impl UpdateGameRequest {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.image_url.is_none()
+            && self.categories.is_none()
+            && self.tags.is_none()
+            && self.screenshots.is_none()
+            && self.trailer_url.is_none()
+            && self.price_lamports.is_none()
+            && self.price_sol.is_none()
+            && self.price_currency.is_none()
+    }
+
+    // `$set` document for the fields present. Switching to SOL clears the mint.
+    pub fn to_set_document(&self) -> Document {
+        let mut set = Document::new();
+        if let Some(name) = &self.name {
+            set.insert("name", name);
+            set.insert("name_normalized", normalized_name(name));
+        }
+        if let Some(description) = &self.description {
+            set.insert("description", description);
+        }
+        if let Some(image_url) = &self.image_url {
+            set.insert("image_url", image_url);
+        }
+        if let Some(categories) = &self.categories {
+            set.insert("categories", categories);
+        }
+        if let Some(tags) = &self.tags {
+            set.insert("tags", tags);
+        }
+        if let Some(screenshots) = &self.screenshots {
+            set.insert("screenshots", screenshots);
+        }
+        if let Some(trailer_url) = &self.trailer_url {
+            set.insert("trailer_url", trailer_url);
+        }
+        if let Some(price) = self.price_lamports {
+            set.insert("price_lamports", price);
+        }
+        if let Some(currency) = &self.price_currency {
+            set.insert("price_currency", currency);
+            set.insert(
+                "price_mint",
+                self.price_mint.clone().map(Bson::String).unwrap_or(Bson::Null),
+            );
+        }
+        set
+    }
+}
+
+impl Game {
+    // Bring a document read from the database up to the current schema
+    pub fn migrate(mut self) -> Self {
+        // v0 -> v1: fields added since (checksums, sizes, launch kind, ...) are
+        // filled by their serde defaults, so only the version needs stamping
+        if self.schema_version < 1 {
+            self.schema_version = 1;
+        }
+        if self.name_normalized.is_empty() {
+            self.name_normalized = normalized_name(&self.name);
+        }
+        // Documents written before updated_at existed
+        if self.updated_at == DateTime::<Utc>::UNIX_EPOCH {
+            self.updated_at = self.created_at;
+        }
+        self
+    }
 }
 
 impl From<CreateGameRequest> for Game {
     fn from(req: CreateGameRequest) -> Self {
+        let now = Utc::now();
         Game {
             _id: None,
+            schema_version: CURRENT_SCHEMA_VERSION,
             collection_address: req.collection_address,
             candy_machine_address: req.candy_machine_address,
+            name_normalized: normalized_name(&req.name),
             name: req.name,
             description: req.description,
             image_url: req.image_url,
             categories: req.categories,
+            tags: req.tags,
+            screenshots: req.screenshots,
+            trailer_url: req.trailer_url,
             executables: req.executables,
             creator: req.creator,
             metadata_uri: req.metadata_uri,
-            price_lamports: req.price_lamports,
-            created_at: Utc::now(),
+            // Only unset for drafts saved with validation warnings
+            price_lamports: req.price_lamports.unwrap_or_default(),
+            price_currency: req.price_currency,
+            price_mint: req.price_mint,
+            status: req.status,
+            created_at: now,
+            updated_at: now,
+            version: 0,
+            download_count: 0,
+            reissue: req.reissue,
+            report_count: 0,
+            flagged: false,
+            score: None,
+            highlight: None,
+        }
+    }
+}
+
+// One successful download, recorded for analytics
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _id: Option<mongodb::bson::oid::ObjectId>,
+    pub game_id: mongodb::bson::oid::ObjectId,
+    pub platform: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    #[serde(with = "fixed_timestamp")]
+    pub ts: DateTime<Utc>,
+}
+
+// A price a game was listed at, recorded on create and on every price change
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PricePoint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _id: Option<mongodb::bson::oid::ObjectId>,
+    pub game_id: mongodb::bson::oid::ObjectId,
+    pub price_lamports: i64,
+    #[serde(with = "fixed_timestamp")]
+    pub ts: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordDownloadRequest {
+    pub platform: String,
+    #[serde(default)]
+    pub wallet: Option<String>,
+}
+
+// Rebuild the create payload from a stored game, to re-run validation on publish
+impl From<&Game> for CreateGameRequest {
+    fn from(game: &Game) -> Self {
+        CreateGameRequest {
+            collection_address: game.collection_address.clone(),
+            candy_machine_address: game.candy_machine_address.clone(),
+            name: game.name.clone(),
+            description: game.description.clone(),
+            image_url: game.image_url.clone(),
+            categories: game.categories.clone(),
+            tags: game.tags.clone(),
+            screenshots: game.screenshots.clone(),
+            trailer_url: game.trailer_url.clone(),
+            executables: game.executables.clone(),
+            creator: game.creator.clone(),
+            metadata_uri: game.metadata_uri.clone(),
+            price_lamports: Some(game.price_lamports),
+            price_sol: None,
+            price_currency: game.price_currency.clone(),
+            price_mint: game.price_mint.clone(),
+            status: game.status,
+            reissue: game.reissue,
+        }
+    }
+}
+
+// Column names for GameCsvRow, in field order
+pub const GAME_CSV_HEADER: [&str; 10] = [
+    "id",
+    "name",
+    "creator",
+    "categories",
+    "tags",
+    "price_lamports",
+    "price_currency",
+    "executables",
+    "created_at",
+    "updated_at",
+];
+
+// Flat spreadsheet view of a game; list fields are joined with `;` and
+// executables are reduced to a count
+#[derive(Debug, Serialize)]
+pub struct GameCsvRow {
+    pub id: String,
+    pub name: String,
+    pub creator: String,
+    pub categories: String,
+    pub tags: String,
+    pub price_lamports: i64,
+    pub price_currency: String,
+    pub executables: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<&Game> for GameCsvRow {
+    fn from(game: &Game) -> Self {
+        GameCsvRow {
+            id: game._id.map(|id| id.to_hex()).unwrap_or_default(),
+            name: game.name.clone(),
+            creator: game.creator.clone(),
+            categories: game.categories.join(";"),
+            tags: game.tags.join(";"),
+            price_lamports: game.price_lamports,
+            price_currency: game.price_currency.clone(),
+            executables: game.executables.len(),
+            created_at: format_timestamp(&game.created_at),
+            updated_at: format_timestamp(&game.updated_at),
         }
     }
 }
+
+// A player's rating and comment on a game; one per reviewer per game
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Review {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _id: Option<mongodb::bson::oid::ObjectId>,
+    pub game_id: mongodb::bson::oid::ObjectId,
+    pub reviewer: String, // wallet address
+    pub rating: i32,      // 1-5
+    #[serde(default)]
+    pub comment: String,
+    #[serde(with = "fixed_timestamp")]
+    pub created_at: DateTime<Utc>,
+    // Distinct wallets that marked the review helpful. The voters themselves
+    // are stored as `helpful_voters` and never returned.
+    #[serde(default)]
+    pub helpful_count: i64,
+}
+
+// A player's report of a malicious or broken game, queued for moderators;
+// one per reporter per game
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _id: Option<mongodb::bson::oid::ObjectId>,
+    pub game_id: mongodb::bson::oid::ObjectId,
+    pub reporter: String, // wallet address
+    pub reason: String,
+    #[serde(with = "fixed_timestamp")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReportRequest {
+    pub reporter: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HelpfulVoteRequest {
+    pub voter: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReviewRequest {
+    pub reviewer: String,
+    pub rating: i32,
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::{self, doc};
+
+    // A game as the first release of the API stored it
+    fn v0_document() -> bson::Document {
+        doc! {
+            "_id": bson::oid::ObjectId::new(),
+            "collection_address": "11111111111111111111111111111111",
+            "candy_machine_address": "11111111111111111111111111111111",
+            "name": "Space Miner",
+            "description": "Mine asteroids",
+            "image_url": "https://example.com/cover.png",
+            "categories": ["sim"],
+            "executables": [{
+                "platform": "x86_64-unknown-linux-gnu",
+                "url": "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            }],
+            "creator": "11111111111111111111111111111111",
+            "metadata_uri": "https://example.com/metadata.json",
+            "price_lamports": 1_000_000_i64,
+            "created_at": "2024-03-01T12:00:00Z",
+        }
+    }
+
+    #[test]
+    fn v0_documents_upgrade_on_read() {
+        let game: Game = bson::from_document(v0_document()).unwrap();
+        assert_eq!(game.schema_version, 0);
+
+        let game = game.migrate();
+        assert_eq!(game.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(game.name_normalized, "space miner");
+        assert_eq!(game.updated_at, game.created_at);
+        assert_eq!(game.status, GameStatus::Published);
+        assert_eq!(game.price_currency, DEFAULT_PRICE_CURRENCY);
+        assert_eq!(game.version, 0);
+        assert!(game.tags.is_empty() && game.screenshots.is_empty());
+        assert_eq!(game.executables[0].launch_kind, LaunchKind::Native);
+        assert!(game.executables[0].sha256.is_none());
+    }
+
+    #[test]
+    fn normalized_names_ignore_case_and_spacing() {
+        assert_eq!(normalized_name("Space Miner"), "space miner");
+        assert_eq!(normalized_name("  SPACE \t Miner\u{7}"), "space miner");
+    }
+
+    #[test]
+    fn migration_is_idempotent() {
+        let game = bson::from_document::<Game>(v0_document())
+            .unwrap()
+            .migrate();
+        let stored = bson::to_document(&game).unwrap();
+        let reread = bson::from_document::<Game>(stored).unwrap().migrate();
+        assert_eq!(reread.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(reread.name_normalized, game.name_normalized);
+        assert_eq!(reread.updated_at, game.updated_at);
+    }
+
+    #[test]
+    fn v0_summaries_get_an_updated_at() {
+        let summary: GameSummary = bson::from_document(v0_document()).unwrap();
+        let summary = summary.migrate();
+        assert_eq!(summary.updated_at, summary.created_at);
+    }
+
+    #[test]
+    fn sol_amounts_round_to_the_nearest_lamport() {
+        // 0.1 * 1e9 is 99_999_999.99999999 in floating point
+        assert_eq!(sol_to_lamports(0.1), Some(100_000_000));
+        assert_eq!(sol_to_lamports(0.3), Some(300_000_000));
+        assert_eq!(sol_to_lamports(1.5), Some(1_500_000_000));
+        assert_eq!(sol_to_lamports(0.000_000_000_4), Some(0));
+        assert_eq!(sol_to_lamports(0.000_000_000_6), Some(1));
+        assert_eq!(sol_to_lamports(0.0), Some(0));
+    }
+
+    #[test]
+    fn invalid_sol_amounts_are_rejected() {
+        assert_eq!(sol_to_lamports(-0.1), None);
+        assert_eq!(sol_to_lamports(f64::NAN), None);
+        assert_eq!(sol_to_lamports(f64::INFINITY), None);
+        assert_eq!(sol_to_lamports(1e12), None);
+    }
+
+    const SCREENSHOT_CID: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+    #[test]
+    fn media_fields_round_trip_through_json() {
+        let request: CreateGameRequest = serde_json::from_value(serde_json::json!({
+            "collection_address": "11111111111111111111111111111111",
+            "candy_machine_address": "11111111111111111111111111111111",
+            "name": "Space Miner",
+            "description": "Mine asteroids",
+            "image_url": "https://example.com/cover.png",
+            "screenshots": [SCREENSHOT_CID, "https://example.com/shot.png"],
+            "trailer_url": "https://example.com/trailer.mp4",
+            "categories": ["sim"],
+            "executables": [],
+            "creator": "11111111111111111111111111111111",
+            "metadata_uri": "https://example.com/metadata.json",
+            "price_lamports": 1_000_000,
+        }))
+        .unwrap();
+        let game = Game::from(request);
+
+        let json = serde_json::to_value(&game).unwrap();
+        assert_eq!(json["image_url"], "https://example.com/cover.png");
+        assert_eq!(
+            json["screenshots"],
+            serde_json::json!([SCREENSHOT_CID, "https://example.com/shot.png"])
+        );
+        assert_eq!(json["trailer_url"], "https://example.com/trailer.mp4");
+
+        let reread: Game = serde_json::from_value(json).unwrap();
+        assert_eq!(reread.screenshots, game.screenshots);
+        assert_eq!(reread.trailer_url, game.trailer_url);
+    }
+
+    #[test]
+    fn missing_trailers_are_left_out() {
+        let game = bson::from_document::<Game>(v0_document())
+            .unwrap()
+            .migrate();
+        let json = serde_json::to_value(&game).unwrap();
+        assert!(json.get("trailer_url").is_none());
+        assert_eq!(json["screenshots"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn updates_set_only_the_media_given() {
+        let update: UpdateGameRequest = serde_json::from_value(serde_json::json!({
+            "expected_version": 0,
+            "screenshots": [SCREENSHOT_CID],
+        }))
+        .unwrap();
+        assert!(!update.is_empty());
+        let set = update.to_set_document();
+        assert_eq!(set.get_array("screenshots").unwrap().len(), 1);
+        assert!(!set.contains_key("trailer_url"));
+        assert!(!set.contains_key("image_url"));
+    }
+}
@@ -0,0 +1,326 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::env_number;
+use crate::http::Timeouts;
+
+// Consecutive failures before the circuit opens, unless RPC_BREAKER_THRESHOLD is set
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+// How long an open circuit fast-fails, unless RPC_BREAKER_COOLDOWN_SECS is set
+const DEFAULT_COOLDOWN_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    // Calls go through; failures are counted
+    Closed,
+    // Calls fast-fail until the cooldown has passed
+    Open,
+    // One trial call is in flight; its result closes or re-opens the circuit.
+    // A trial that never reports back (its request was dropped) is replaced
+    // by a new one after `trial_timeout`.
+    HalfOpen,
+}
+
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_started_at: Option<Instant>,
+}
+
+// Keeps a flaky RPC from dragging every request down with it: after
+// `threshold` consecutive failures calls are refused for `cooldown`, then a
+// single trial call decides whether to close the circuit again
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    // Longest a trial call can take: the HTTP client's request timeout
+    trial_timeout: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration, trial_timeout: Duration) -> Self {
+        CircuitBreaker {
+            threshold: threshold.max(1),
+            cooldown,
+            trial_timeout,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                trial_started_at: None,
+            }),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.inner
+            .lock()
+            .map(|inner| inner.state)
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    // Whether a call may go out now. An open circuit past its cooldown moves
+    // to half-open and lets exactly this one call through as the trial; so
+    // does a half-open circuit whose trial has outlived `trial_timeout`.
+    pub fn allow(&self) -> bool {
+        let Ok(mut inner) = self.inner.lock() else {
+            return true;
+        };
+
+        let start_trial = match inner.state {
+            CircuitState::Closed => return true,
+            CircuitState::HalfOpen => inner
+                .trial_started_at
+                .is_none_or(|started_at| started_at.elapsed() >= self.trial_timeout),
+            CircuitState::Open => inner
+                .opened_at
+                .is_none_or(|opened_at| opened_at.elapsed() >= self.cooldown),
+        };
+        if start_trial {
+            inner.state = CircuitState::HalfOpen;
+            inner.trial_started_at = Some(Instant::now());
+        }
+        start_trial
+    }
+
+    // Seconds until an open circuit allows a trial call, or a half-open one
+    // gives up on its trial, for Retry-After
+    pub fn retry_after_secs(&self) -> u64 {
+        let Ok(inner) = self.inner.lock() else {
+            return 0;
+        };
+        let (wait, since) = match inner.state {
+            CircuitState::Open => (self.cooldown, inner.opened_at),
+            CircuitState::HalfOpen => (self.trial_timeout, inner.trial_started_at),
+            CircuitState::Closed => return 0,
+        };
+        since.map_or(0, |since| {
+            wait.saturating_sub(since.elapsed()).as_secs().max(1)
+        })
+    }
+
+    pub fn record_success(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            if inner.state != CircuitState::Closed {
+                println!("[BACKEND] Solana RPC recovered, closing circuit");
+            }
+            inner.state = CircuitState::Closed;
+            inner.consecutive_failures = 0;
+            inner.opened_at = None;
+            inner.trial_started_at = None;
+        }
+    }
+
+    pub fn record_failure(&self) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let trip = match inner.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => inner.consecutive_failures >= self.threshold,
+            CircuitState::Open => false,
+        };
+        if trip {
+            eprintln!(
+                "[BACKEND] Solana RPC failed {} time(s) in a row, opening circuit for {}s",
+                inner.consecutive_failures,
+                self.cooldown.as_secs()
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            inner.trial_started_at = None;
+        }
+    }
+}
+
+pub enum RpcError {
+    // SOLANA_RPC_URL isn't set
+    NotConfigured,
+    // The breaker is refusing calls; retry after this many seconds
+    CircuitOpen(u64),
+    Failed(String),
+}
+
+// JSON-RPC client for the Solana cluster in SOLANA_RPC_URL, with every call
+// going through a shared circuit breaker
+#[derive(Clone)]
+pub struct SolanaRpc {
+    client: reqwest::Client,
+    url: Option<Arc<String>>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl SolanaRpc {
+    // Calls go through the shared `client`, which bounds each one by
+    // `timeouts.request`
+    pub fn from_env(client: reqwest::Client, timeouts: Timeouts) -> Self {
+        let url = env::var("SOLANA_RPC_URL")
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        let breaker = CircuitBreaker::new(
+            env_number("RPC_BREAKER_THRESHOLD", DEFAULT_FAILURE_THRESHOLD),
+            Duration::from_secs(env_number(
+                "RPC_BREAKER_COOLDOWN_SECS",
+                DEFAULT_COOLDOWN_SECS,
+            )),
+            timeouts.request,
+        );
+        if url.is_some() {
+            println!(
+                "Solana RPC: circuit opens after {} consecutive failures for {}s",
+                breaker.threshold,
+                breaker.cooldown.as_secs()
+            );
+        }
+
+        SolanaRpc {
+            client,
+            url: url.map(Arc::new),
+            breaker: Arc::new(breaker),
+        }
+    }
+
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    // Make a JSON-RPC call and return its `result`. Transport errors, non-2xx
+    // responses and JSON-RPC errors all count against the breaker.
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let url = self.url.as_deref().ok_or(RpcError::NotConfigured)?;
+        if !self.breaker.allow() {
+            return Err(RpcError::CircuitOpen(self.breaker.retry_after_secs()));
+        }
+
+        let result = self.send(url, method, params).await;
+        match &result {
+            Ok(_) => self.breaker.record_success(),
+            Err(RpcError::Failed(e)) => {
+                eprintln!("[BACKEND] Solana RPC {} failed: {}", method, e);
+                self.breaker.record_failure();
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    async fn send(&self, url: &str, method: &str, params: Value) -> Result<Value, RpcError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| RpcError::Failed(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(RpcError::Failed(format!("HTTP {}", response.status())));
+        }
+
+        let mut reply: Value = response
+            .json()
+            .await
+            .map_err(|e| RpcError::Failed(format!("Invalid response: {}", e)))?;
+        if let Some(error) = reply.get("error") {
+            return Err(RpcError::Failed(error.to_string()));
+        }
+        Ok(reply
+            .get_mut("result")
+            .map(Value::take)
+            .unwrap_or(Value::Null))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    const COOLDOWN: Duration = Duration::from_millis(20);
+
+    fn breaker(threshold: u32) -> CircuitBreaker {
+        CircuitBreaker::new(threshold, COOLDOWN, Duration::from_millis(50))
+    }
+
+    fn trip(breaker: &CircuitBreaker) {
+        for _ in 0..breaker.threshold {
+            assert!(breaker.allow());
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn opens_after_threshold_consecutive_failures() {
+        let breaker = breaker(3);
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow());
+        assert!(breaker.retry_after_secs() >= 1);
+    }
+
+    #[test]
+    fn successful_trial_closes_the_circuit() {
+        let breaker = breaker(2);
+        trip(&breaker);
+        sleep(COOLDOWN);
+
+        assert!(breaker.allow());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(!breaker.allow(), "only one trial at a time");
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow());
+        assert_eq!(breaker.retry_after_secs(), 0);
+    }
+
+    #[test]
+    fn failed_trial_reopens_the_circuit() {
+        let breaker = breaker(2);
+        trip(&breaker);
+        sleep(COOLDOWN);
+
+        assert!(breaker.allow());
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow(), "a failed trial starts a new cooldown");
+        sleep(COOLDOWN);
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn abandoned_trial_is_replaced() {
+        let breaker = breaker(1);
+        trip(&breaker);
+        sleep(COOLDOWN);
+
+        // The trial call is dropped before it records a result
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+        sleep(breaker.trial_timeout);
+        assert!(breaker.allow());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
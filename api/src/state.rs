@@ -0,0 +1,54 @@
+use axum::extract::FromRef;
+use mongodb::Database;
+use std::sync::Arc;
+
+use crate::admin::AdminToken;
+use crate::cache::ListingCache;
+use crate::rpc::SolanaRpc;
+use crate::webhooks::Webhooks;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub db: Database,
+    // Shared client for outbound calls, configured by HTTP_*_TIMEOUT_SECS;
+    // webhooks and rpc hold clones of it
+    pub http: reqwest::Client,
+    pub cache: Arc<ListingCache>,
+    // Ordered public IPFS gateway base URLs recommended to clients
+    pub gateways: Arc<Vec<String>>,
+    // Oldest launcher version this API still works with
+    pub min_client_version: Arc<String>,
+    pub webhooks: Webhooks,
+    pub rpc: SolanaRpc,
+    pub admin_token: AdminToken,
+}
+
+impl FromRef<AppState> for Database {
+    fn from_ref(state: &AppState) -> Self {
+        state.db.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ListingCache> {
+    fn from_ref(state: &AppState) -> Self {
+        state.cache.clone()
+    }
+}
+
+impl FromRef<AppState> for Webhooks {
+    fn from_ref(state: &AppState) -> Self {
+        state.webhooks.clone()
+    }
+}
+
+impl FromRef<AppState> for SolanaRpc {
+    fn from_ref(state: &AppState) -> Self {
+        state.rpc.clone()
+    }
+}
+
+impl FromRef<AppState> for AdminToken {
+    fn from_ref(state: &AppState) -> Self {
+        state.admin_token.clone()
+    }
+}
@@ -0,0 +1,73 @@
+// Helpers for the `#[ignore]`d tests that need a MongoDB; run them with
+// `cargo test -- --ignored` against MONGODB_URI (localhost by default)
+use axum::body::Body;
+use axum::response::Response;
+use mongodb::{Client, Database};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::db;
+use crate::models::{CreateGameRequest, Game};
+
+static DATABASE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// A fresh, indexed database per test; drop it with `db.drop().await` at the end
+pub async fn test_db() -> Database {
+    let db = unindexed_test_db().await;
+    db::ensure_indexes(&db).await.expect("indexes");
+    db
+}
+
+// A fresh database without the API's indexes, for testing index setup
+pub async fn unindexed_test_db() -> Database {
+    let uri =
+        std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".to_string());
+    let client = Client::with_uri_str(&uri).await.expect("MongoDB client");
+    let name = format!(
+        "gamex_test_{}_{}",
+        std::process::id(),
+        DATABASE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    client.database(&name)
+}
+
+// For handlers that return before touching the database; the client only
+// connects on its first operation, so no server is needed
+pub async fn unconnected_db() -> Database {
+    Client::with_uri_str("mongodb://localhost:27017")
+        .await
+        .expect("MongoDB client")
+        .database("gamex_test_unconnected")
+}
+
+// A valid, published create payload with the given listing fields
+pub fn game_request(name: &str, categories: &[&str], tags: &[&str]) -> CreateGameRequest {
+    serde_json::from_value(json!({
+        "collection_address": "11111111111111111111111111111111",
+        "candy_machine_address": "11111111111111111111111111111111",
+        "name": name,
+        "description": format!("{} description", name),
+        "image_url": "https://example.com/cover.png",
+        "categories": categories,
+        "tags": tags,
+        "executables": [{
+            "platform": "x86_64-unknown-linux-gnu",
+            "url": "https://example.com/game.tar.gz",
+        }],
+        "creator": "11111111111111111111111111111111",
+        "metadata_uri": "https://example.com/metadata.json",
+        "price_lamports": 1_000_000,
+    }))
+    .expect("game request")
+}
+
+pub fn game(name: &str, categories: &[&str], tags: &[&str]) -> Game {
+    Game::from(game_request(name, categories, tags))
+}
+
+pub async fn body_json(response: Response<Body>) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("response body");
+    serde_json::from_slice(&bytes).expect("JSON body")
+}
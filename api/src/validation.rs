@@ -0,0 +1,747 @@
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::models::{
+    is_supported_platform, is_valid_sha256, sol_to_lamports, CreateGameRequest,
+    CreateReportRequest, CreateReviewRequest, LaunchKind, UpdateGameRequest,
+    DEFAULT_PRICE_CURRENCY,
+};
+
+// Longest game name accepted after normalization
+pub const MAX_NAME_LENGTH: usize = 200;
+
+pub const MAX_TAGS: usize = 20;
+pub const MAX_TAG_LENGTH: usize = 32;
+pub const MAX_SCREENSHOTS: usize = 20;
+pub const MIN_RATING: i32 = 1;
+pub const MAX_RATING: i32 = 5;
+pub const MAX_REVIEW_LENGTH: usize = 2000;
+pub const MAX_REPORT_REASON_LENGTH: usize = 1000;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+// Collects every validation failure so they can be reported together
+#[derive(Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn error(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn check(&mut self, ok: bool, field: impl Into<String>, message: impl Into<String>) {
+        if !ok {
+            self.error(field, message);
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Vec<FieldError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+// Trim, drop control characters and collapse internal whitespace runs
+pub fn clean_name(raw: &str) -> String {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| !c.is_control() || c.is_whitespace())
+        .collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// clean_name, then the length limits
+pub fn normalize_name(raw: &str) -> Result<String, String> {
+    let name = clean_name(raw);
+
+    if name.is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "Name must be at most {} characters",
+            MAX_NAME_LENGTH
+        ));
+    }
+
+    Ok(name)
+}
+
+// Trim and lowercase a tag, as stored and as matched by search
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+// Normalized tags with blanks and duplicates dropped, keeping first-seen order
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.iter()
+        .map(|tag| normalize_tag(tag))
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.clone()))
+        .collect()
+}
+
+// Base58 string of the length a 32-byte Solana public key encodes to
+pub fn is_valid_pubkey(value: &str) -> bool {
+    (32..=44).contains(&value.len()) && value.chars().all(|c| BASE58_ALPHABET.contains(c))
+}
+
+// Ticker-style code such as SOL or USDC
+pub fn is_valid_currency_code(value: &str) -> bool {
+    (1..=10).contains(&value.len()) && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+pub fn is_valid_url(value: &str) -> bool {
+    ["http://", "https://", "ipfs://"]
+        .iter()
+        .any(|scheme| value.len() > scheme.len() && value.starts_with(scheme))
+}
+
+// CID from an `ipfs://` URI or a gateway URL (`.../ipfs/<cid>`)
+pub fn extract_cid(url: &str) -> Option<String> {
+    let rest = match url.strip_prefix("ipfs://") {
+        Some(rest) => rest,
+        None => url.split_once("/ipfs/")?.1,
+    };
+    let cid: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    Some(cid).filter(|cid| !cid.is_empty())
+}
+
+// Bare CID as stored for gallery media: CIDv0 (Qm...) or base32 CIDv1 (bafy...)
+fn is_bare_cid(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_alphanumeric())
+        && ((value.len() == 46 && value.starts_with("Qm"))
+            || (value.len() >= 50 && value.starts_with('b')))
+}
+
+// Screenshots are a bare CID or a URL; errors name the entry by index
+fn check_screenshots(validator: &mut Validator, screenshots: &mut [String]) {
+    for screenshot in screenshots.iter_mut() {
+        *screenshot = screenshot.trim().to_string();
+    }
+    validator.check(
+        screenshots.len() <= MAX_SCREENSHOTS,
+        "screenshots",
+        format!("At most {} screenshots are allowed", MAX_SCREENSHOTS),
+    );
+    for (index, screenshot) in screenshots.iter().enumerate() {
+        validator.check(
+            is_valid_url(screenshot) || is_bare_cid(screenshot),
+            format!("screenshots[{}]", index),
+            "Must be a CID or an http(s) or ipfs:// URL",
+        );
+    }
+}
+
+// `scheme://target` URI handed to an external launcher (e.g. steam://run/440)
+pub fn is_valid_launch_uri(value: &str) -> bool {
+    match value.split_once("://") {
+        Some((scheme, target)) => {
+            !target.is_empty()
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !["http", "https", "file"].contains(&scheme.to_ascii_lowercase().as_str())
+        }
+        None => false,
+    }
+}
+
+// Normalize the currency code in place and check the mint matches it
+fn check_price_currency(validator: &mut Validator, currency: &mut String, mint: &Option<String>) {
+    *currency = currency.trim().to_ascii_uppercase();
+    validator.check(
+        is_valid_currency_code(currency),
+        "price_currency",
+        "Currency must be 1-10 letters or digits",
+    );
+    if currency == DEFAULT_PRICE_CURRENCY {
+        validator.check(
+            mint.is_none(),
+            "price_mint",
+            "Mint must not be set for SOL prices",
+        );
+    } else {
+        match mint {
+            Some(mint) => validator.check(
+                is_valid_pubkey(mint),
+                "price_mint",
+                "Must be a valid Solana address",
+            ),
+            None => validator.error("price_mint", "Mint is required for token prices"),
+        }
+    }
+}
+
+// Convert price_sol into price_lamports. Sending both is only accepted when
+// they agree.
+fn resolve_price_sol(validator: &mut Validator, lamports: &mut Option<i64>, sol: Option<f64>) {
+    let Some(sol) = sol else {
+        return;
+    };
+    match (sol_to_lamports(sol), *lamports) {
+        (None, _) => validator.error("price_sol", "Must be a non-negative amount of SOL"),
+        (Some(converted), Some(existing)) if converted != existing => {
+            validator.error("price_sol", "Conflicts with price_lamports")
+        }
+        (Some(converted), _) => *lamports = Some(converted),
+    }
+}
+
+// Normalize tags in place and enforce the count and length limits
+fn check_tags(validator: &mut Validator, tags: &mut Vec<String>) {
+    *tags = normalize_tags(tags);
+    validator.check(
+        tags.len() <= MAX_TAGS,
+        "tags",
+        format!("At most {} tags are allowed", MAX_TAGS),
+    );
+    for tag in tags.iter() {
+        validator.check(
+            tag.chars().count() <= MAX_TAG_LENGTH,
+            format!("tags.{}", tag),
+            format!("Tags must be at most {} characters", MAX_TAG_LENGTH),
+        );
+    }
+}
+
+// Validate a create request, normalizing its name in place
+pub fn validate_create_request(req: &mut CreateGameRequest) -> Result<(), Vec<FieldError>> {
+    let mut validator = Validator::new();
+
+    match normalize_name(&req.name) {
+        Ok(name) => req.name = name,
+        Err(e) => validator.error("name", e),
+    }
+
+    for (field, value) in [
+        ("collection_address", &req.collection_address),
+        ("candy_machine_address", &req.candy_machine_address),
+        ("creator", &req.creator),
+    ] {
+        validator.check(is_valid_pubkey(value), field, "Must be a valid Solana address");
+    }
+
+    resolve_price_sol(&mut validator, &mut req.price_lamports, req.price_sol);
+    match req.price_lamports {
+        Some(price) => validator.check(price >= 0, "price_lamports", "Price must not be negative"),
+        None => validator.error(
+            "price_lamports",
+            "Either price_lamports or price_sol is required",
+        ),
+    }
+    check_price_currency(&mut validator, &mut req.price_currency, &req.price_mint);
+    validator.check(
+        req.price_sol.is_none() || req.price_currency == DEFAULT_PRICE_CURRENCY,
+        "price_sol",
+        "Only valid for SOL prices",
+    );
+
+    for (field, value) in [
+        ("image_url", Some(&req.image_url)),
+        ("metadata_uri", Some(&req.metadata_uri)),
+        ("trailer_url", req.trailer_url.as_ref()),
+    ] {
+        if let Some(value) = value {
+            validator.check(
+                is_valid_url(value),
+                field,
+                "Must be an http(s) or ipfs:// URL",
+            );
+        }
+    }
+
+    check_tags(&mut validator, &mut req.tags);
+    check_screenshots(&mut validator, &mut req.screenshots);
+
+    validator.check(
+        !req.executables.is_empty(),
+        "executables",
+        "At least one executable is required",
+    );
+
+    // Errors name the entry by array index (`executables[1].url`) so a typo in
+    // one platform's entry is easy to find
+    let mut platforms = HashSet::new();
+    for (index, executable) in req.executables.iter().enumerate() {
+        let field = format!("executables[{}]", index);
+
+        validator.check(
+            is_supported_platform(&executable.platform),
+            format!("{}.platform", field),
+            format!("Unsupported platform: {}", executable.platform),
+        );
+        validator.check(
+            platforms.insert(executable.platform.as_str()),
+            format!("{}.platform", field),
+            format!("Duplicate platform: {}", executable.platform),
+        );
+        match executable.launch_kind {
+            LaunchKind::Native => validator.check(
+                is_valid_url(&executable.url)
+                    && (!executable.url.starts_with("ipfs://")
+                        || extract_cid(&executable.url).is_some()),
+                format!("{}.url", field),
+                "Must be an ipfs://<cid>, gateway or http(s) URL",
+            ),
+            LaunchKind::Uri => validator.check(
+                is_valid_launch_uri(&executable.url),
+                format!("{}.url", field),
+                "Must be a protocol URI such as steam://run/<app-id>",
+            ),
+        }
+        if let Some(checksum) = &executable.sha256 {
+            validator.check(
+                is_valid_sha256(checksum),
+                format!("{}.sha256", field),
+                "Must be 64 hex characters",
+            );
+        }
+    }
+
+    validator.finish()
+}
+
+// Validate the fields present in a partial update, normalizing them in place
+pub fn validate_update_request(req: &mut UpdateGameRequest) -> Result<(), Vec<FieldError>> {
+    let mut validator = Validator::new();
+
+    if let Some(name) = &req.name {
+        match normalize_name(name) {
+            Ok(name) => req.name = Some(name),
+            Err(e) => validator.error("name", e),
+        }
+    }
+
+    resolve_price_sol(&mut validator, &mut req.price_lamports, req.price_sol);
+    if let Some(price) = req.price_lamports {
+        validator.check(price >= 0, "price_lamports", "Price must not be negative");
+    }
+
+    // The mint only makes sense alongside the currency it belongs to
+    match &mut req.price_currency {
+        Some(currency) => {
+            check_price_currency(&mut validator, currency, &req.price_mint);
+            validator.check(
+                req.price_sol.is_none() || currency == DEFAULT_PRICE_CURRENCY,
+                "price_sol",
+                "Only valid for SOL prices",
+            );
+        }
+        None => validator.check(
+            req.price_mint.is_none(),
+            "price_mint",
+            "Mint can only be changed together with price_currency",
+        ),
+    }
+
+    for (field, value) in [
+        ("image_url", &req.image_url),
+        ("trailer_url", &req.trailer_url),
+    ] {
+        if let Some(value) = value {
+            validator.check(
+                is_valid_url(value),
+                field,
+                "Must be an http(s) or ipfs:// URL",
+            );
+        }
+    }
+
+    if let Some(tags) = &mut req.tags {
+        check_tags(&mut validator, tags);
+    }
+
+    if let Some(screenshots) = &mut req.screenshots {
+        check_screenshots(&mut validator, screenshots);
+    }
+
+    validator.check(!req.is_empty(), "body", "No fields to update");
+
+    validator.finish()
+}
+
+// Validate a review, trimming the comment in place
+pub fn validate_review_request(req: &mut CreateReviewRequest) -> Result<(), Vec<FieldError>> {
+    let mut validator = Validator::new();
+
+    validator.check(
+        is_valid_pubkey(&req.reviewer),
+        "reviewer",
+        "Must be a valid Solana address",
+    );
+    validator.check(
+        (MIN_RATING..=MAX_RATING).contains(&req.rating),
+        "rating",
+        format!("Rating must be between {} and {}", MIN_RATING, MAX_RATING),
+    );
+
+    req.comment = req.comment.trim().to_string();
+    validator.check(
+        req.comment.chars().count() <= MAX_REVIEW_LENGTH,
+        "comment",
+        format!("Comment must be at most {} characters", MAX_REVIEW_LENGTH),
+    );
+
+    validator.finish()
+}
+
+pub fn validate_report_request(req: &mut CreateReportRequest) -> Result<(), Vec<FieldError>> {
+    let mut validator = Validator::new();
+
+    validator.check(
+        is_valid_pubkey(&req.reporter),
+        "reporter",
+        "Must be a valid Solana address",
+    );
+
+    req.reason = req.reason.trim().to_string();
+    validator.check(!req.reason.is_empty(), "reason", "Reason is required");
+    validator.check(
+        req.reason.chars().count() <= MAX_REPORT_REASON_LENGTH,
+        "reason",
+        format!("Reason must be at most {} characters", MAX_REPORT_REASON_LENGTH),
+    );
+
+    validator.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::game_request;
+
+    fn error_fields(result: Result<(), Vec<FieldError>>) -> Vec<String> {
+        result
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|error| error.field)
+            .collect()
+    }
+
+    #[test]
+    fn names_are_trimmed_and_collapsed() {
+        assert_eq!(
+            normalize_name("  Space   Miner \t"),
+            Ok("Space Miner".to_string())
+        );
+        assert_eq!(
+            normalize_name("Space\u{7}\nMiner"),
+            Ok("Space Miner".to_string())
+        );
+        assert_eq!(
+            normalize_name("Space\u{0}Miner"),
+            Ok("SpaceMiner".to_string())
+        );
+    }
+
+    #[test]
+    fn whitespace_only_names_are_rejected() {
+        assert!(normalize_name("").is_err());
+        assert!(normalize_name(" \t\n ").is_err());
+        assert!(normalize_name("\u{1b}\u{7}").is_err());
+    }
+
+    #[test]
+    fn over_long_names_are_rejected() {
+        let longest = "x".repeat(MAX_NAME_LENGTH);
+        assert_eq!(normalize_name(&longest), Ok(longest.clone()));
+        assert!(normalize_name(&format!("{}x", longest)).is_err());
+        // Counted in characters, not bytes
+        assert!(normalize_name(&"é".repeat(MAX_NAME_LENGTH)).is_ok());
+        // Collapsed whitespace doesn't count against the limit
+        assert!(normalize_name(&format!("a{}b", " ".repeat(MAX_NAME_LENGTH))).is_ok());
+    }
+
+    #[test]
+    fn create_and_update_store_the_cleaned_name() {
+        let mut create = game_request("  Space   Miner ", &["sim"], &[]);
+        assert!(validate_create_request(&mut create).is_ok());
+        assert_eq!(create.name, "Space Miner");
+
+        let mut blank = game_request("   ", &["sim"], &[]);
+        assert_eq!(error_fields(validate_create_request(&mut blank)), ["name"]);
+
+        let mut update: UpdateGameRequest = serde_json::from_value(
+            serde_json::json!({ "expected_version": 0, "name": " Kart  Rush " }),
+        )
+        .unwrap();
+        assert!(validate_update_request(&mut update).is_ok());
+        assert_eq!(update.name.as_deref(), Some("Kart Rush"));
+    }
+
+    #[test]
+    fn validator_collects_every_failure() {
+        let mut validator = Validator::new();
+        validator.check(true, "name", "unused");
+        validator.check(false, "creator", "Must be a valid Solana address");
+        validator.error(
+            "executables[1].url",
+            "Must be an ipfs://<cid>, gateway or http(s) URL",
+        );
+        let errors = validator.finish().unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| error.field.as_str())
+                .collect::<Vec<_>>(),
+            ["creator", "executables[1].url"]
+        );
+        assert!(Validator::new().finish().is_ok());
+    }
+
+    const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    #[test]
+    fn sol_prices_take_no_mint() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.price_currency = " sol ".to_string();
+        assert!(validate_create_request(&mut request).is_ok());
+        assert_eq!(request.price_currency, "SOL");
+
+        request.price_mint = Some(USDC_MINT.to_string());
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_mint"]
+        );
+    }
+
+    #[test]
+    fn token_prices_need_a_valid_mint() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.price_currency = "usdc".to_string();
+        request.price_mint = Some(USDC_MINT.to_string());
+        assert!(validate_create_request(&mut request).is_ok());
+        assert_eq!(request.price_currency, "USDC");
+        // Token prices stay in base units, untouched by SOL conversion
+        assert_eq!(request.price_lamports, Some(1_000_000));
+
+        request.price_mint = None;
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_mint"]
+        );
+        request.price_mint = Some("not-a-mint".to_string());
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_mint"]
+        );
+        request.price_mint = Some(USDC_MINT.to_string());
+        request.price_currency = "US DOLLAR COIN".to_string();
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_currency"]
+        );
+    }
+
+    #[test]
+    fn price_sol_only_applies_to_sol_prices() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.price_lamports = None;
+        request.price_sol = Some(1.5);
+        assert!(validate_create_request(&mut request).is_ok());
+        assert_eq!(request.price_lamports, Some(1_500_000_000));
+
+        request.price_lamports = None;
+        request.price_currency = "USDC".to_string();
+        request.price_mint = Some(USDC_MINT.to_string());
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_sol"]
+        );
+    }
+
+    #[test]
+    fn updates_change_the_mint_only_with_the_currency() {
+        let update = |body: serde_json::Value| {
+            let mut request: UpdateGameRequest = serde_json::from_value(body).unwrap();
+            error_fields(validate_update_request(&mut request))
+        };
+        assert_eq!(
+            update(
+                serde_json::json!({ "expected_version": 1, "name": "Space Miner", "price_mint": USDC_MINT })
+            ),
+            ["price_mint"]
+        );
+        assert!(update(serde_json::json!({
+            "expected_version": 1,
+            "price_currency": "usdc",
+            "price_mint": USDC_MINT,
+        }))
+        .is_empty());
+        assert_eq!(
+            update(serde_json::json!({
+                "expected_version": 1,
+                "price_currency": "USDC",
+                "price_sol": 2.0,
+                "price_mint": USDC_MINT,
+            })),
+            ["price_sol"]
+        );
+    }
+
+    #[test]
+    fn price_sol_must_agree_with_price_lamports() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.price_lamports = Some(100_000_000);
+        request.price_sol = Some(0.1);
+        assert!(validate_create_request(&mut request).is_ok());
+        assert_eq!(request.price_lamports, Some(100_000_000));
+
+        request.price_lamports = Some(99_999_999);
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_sol"]
+        );
+
+        request.price_lamports = None;
+        request.price_sol = Some(-1.0);
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["price_sol", "price_lamports"]
+        );
+    }
+
+    #[test]
+    fn updates_convert_price_sol() {
+        let mut request: UpdateGameRequest =
+            serde_json::from_value(serde_json::json!({ "expected_version": 1, "price_sol": 0.1 }))
+                .unwrap();
+        assert!(validate_update_request(&mut request).is_ok());
+        assert_eq!(request.price_lamports, Some(100_000_000));
+    }
+
+    #[test]
+    fn executable_errors_name_the_failing_index() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.executables = serde_json::from_value(serde_json::json!([
+            { "platform": "x86_64-unknown-linux-gnu", "url": "https://example.com/miner" },
+            { "platform": "x86_64-pc-windoze", "url": "miner.exe" },
+        ]))
+        .unwrap();
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["executables[1].platform", "executables[1].url"]
+        );
+    }
+
+    #[test]
+    fn each_media_url_is_validated() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.screenshots = vec![
+            " QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG ".to_string(),
+            "shot.png".to_string(),
+            "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG/2.png".to_string(),
+            "https://example.com/shot.png".to_string(),
+        ];
+        request.trailer_url = Some("trailer.mp4".to_string());
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["trailer_url", "screenshots[1]"]
+        );
+        assert_eq!(
+            request.screenshots[0],
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+        );
+
+        request.screenshots.remove(1);
+        request.trailer_url = Some("https://example.com/trailer.mp4".to_string());
+        assert!(validate_create_request(&mut request).is_ok());
+    }
+
+    #[test]
+    fn screenshot_galleries_are_capped() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        request.screenshots = vec!["https://example.com/shot.png".to_string(); MAX_SCREENSHOTS];
+        assert!(validate_create_request(&mut request).is_ok());
+        request
+            .screenshots
+            .push("https://example.com/shot.png".to_string());
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["screenshots"]
+        );
+    }
+
+    #[test]
+    fn updates_validate_media_urls() {
+        let mut request: UpdateGameRequest = serde_json::from_value(serde_json::json!({
+            "expected_version": 1,
+            "screenshots": ["https://example.com/shot.png", "ftp://example.com/shot.png"],
+            "trailer_url": "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        }))
+        .unwrap();
+        assert_eq!(
+            error_fields(validate_update_request(&mut request)),
+            ["screenshots[1]"]
+        );
+    }
+
+    fn tags(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn tags_are_normalized_and_deduplicated() {
+        assert_eq!(
+            normalize_tags(&tags(&[
+                " Co-op ",
+                "pixel art",
+                "",
+                "CO-OP",
+                "  ",
+                "Roguelike"
+            ])),
+            ["co-op", "pixel art", "roguelike"]
+        );
+        assert!(normalize_tags(&[]).is_empty());
+    }
+
+    #[test]
+    fn tag_limits_apply_after_normalization() {
+        let mut request = game_request("Space Miner", &["sim"], &[]);
+        // Duplicates collapse before the count is checked
+        request.tags = vec!["Co-op".to_string(); MAX_TAGS + 5];
+        assert!(validate_create_request(&mut request).is_ok());
+        assert_eq!(request.tags, ["co-op"]);
+
+        request.tags = (0..=MAX_TAGS)
+            .map(|index| format!("tag-{}", index))
+            .collect();
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            ["tags"]
+        );
+
+        let long = "x".repeat(MAX_TAG_LENGTH + 1);
+        request.tags = vec![long.clone(), "x".repeat(MAX_TAG_LENGTH)];
+        assert_eq!(
+            error_fields(validate_create_request(&mut request)),
+            [format!("tags.{}", long)]
+        );
+    }
+}
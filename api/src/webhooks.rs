@@ -0,0 +1,151 @@
+use serde_json::json;
+use std::env;
+use std::sync::Arc;
+
+use crate::models::{Game, GameSummary};
+
+// Outbound notifications for integrators (Discord bots, indexers), configured
+// with a comma-separated WEBHOOK_URLS. Unset means no webhooks.
+#[derive(Clone)]
+pub struct Webhooks {
+    client: reqwest::Client,
+    urls: Arc<Vec<String>>,
+}
+
+fn parse_urls(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .filter(|url| {
+            let valid = url.starts_with("http://") || url.starts_with("https://");
+            if !valid {
+                eprintln!("Ignoring invalid webhook URL: {}", url);
+            }
+            valid
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+impl Webhooks {
+    // Deliveries go through the shared `client` and its timeouts
+    pub fn from_env(client: reqwest::Client) -> Self {
+        let urls = parse_urls(&env::var("WEBHOOK_URLS").unwrap_or_default());
+        if !urls.is_empty() {
+            println!("Webhooks: notifying {} URL(s) of new games", urls.len());
+        }
+
+        Webhooks {
+            client,
+            urls: Arc::new(urls),
+        }
+    }
+
+    // POST `{"event": "game.published", "game": <summary>}` to every webhook.
+    // Deliveries run in the background; failures are only logged.
+    pub fn notify_webhooks(&self, game: &Game) {
+        if self.urls.is_empty() {
+            return;
+        }
+
+        let payload = json!({
+            "event": "game.published",
+            "game": GameSummary::from(game),
+        });
+        for url in self.urls.iter() {
+            let request = self.client.post(url).json(&payload);
+            let url = url.clone();
+            tokio::spawn(async move {
+                match request.send().await {
+                    Ok(response) if response.status().is_success() => {}
+                    Ok(response) => {
+                        eprintln!("[BACKEND] Webhook {} responded {}", url, response.status())
+                    }
+                    Err(e) => eprintln!("[BACKEND] Webhook {} failed: {}", url, e),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, routing::post, Json, Router};
+    use serde_json::Value;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    use crate::test_support::game;
+
+    // Receiver that forwards every delivered body to the returned channel
+    async fn mock_receiver() -> (String, mpsc::UnboundedReceiver<Value>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(sender): State<mpsc::UnboundedSender<Value>>,
+                     Json(body): Json<Value>| async move {
+                        sender.send(body).unwrap();
+                    },
+                ),
+            )
+            .with_state(sender);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, receiver)
+    }
+
+    fn webhooks(urls: Vec<String>) -> Webhooks {
+        Webhooks {
+            client: reqwest::Client::new(),
+            urls: Arc::new(urls),
+        }
+    }
+
+    #[test]
+    fn only_http_urls_are_kept() {
+        assert_eq!(
+            parse_urls(" https://a.example.com/hook, ,ftp://b.example.com,http://c.local "),
+            ["https://a.example.com/hook", "http://c.local"]
+        );
+        assert!(parse_urls("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn published_games_are_posted_to_every_webhook() {
+        let (first_url, mut first) = mock_receiver().await;
+        let (second_url, mut second) = mock_receiver().await;
+        webhooks(vec![first_url, second_url]).notify_webhooks(&game("Space Miner", &["sim"], &[]));
+
+        for receiver in [&mut first, &mut second] {
+            let body = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(body["event"], "game.published");
+            assert_eq!(body["game"]["name"], "Space Miner");
+            // Deliveries carry the summary, not the full document
+            assert!(body["game"].get("executables").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_do_not_stop_the_others() {
+        let (url, mut receiver) = mock_receiver().await;
+        // Nothing listens on port 9 of localhost
+        webhooks(vec!["http://127.0.0.1:9/hook".to_string(), url]).notify_webhooks(&game(
+            "Space Miner",
+            &["sim"],
+            &[],
+        ));
+
+        let body = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(body["game"]["name"], "Space Miner");
+    }
+}
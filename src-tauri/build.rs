@@ -1,3 +1,32 @@
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+// Bake the bundled IPFS sidecar's SHA-256 into the launcher so a replaced or
+// corrupted binary is caught at startup (see integrity::verify_ipfs_sidecar)
+fn embed_sidecar_checksum() {
+    let target = std::env::var("TARGET").unwrap_or_default();
+    let suffix = if target.contains("windows") {
+        ".exe"
+    } else {
+        ""
+    };
+    let sidecar = PathBuf::from(format!("binaries/ipfs-{}{}", target, suffix));
+    println!("cargo:rerun-if-changed={}", sidecar.display());
+
+    match std::fs::read(&sidecar) {
+        Ok(bytes) => println!(
+            "cargo:rustc-env=IPFS_SIDECAR_SHA256={:x}",
+            Sha256::digest(&bytes)
+        ),
+        Err(e) => println!(
+            "cargo:warning=IPFS sidecar {} not hashed: {}",
+            sidecar.display(),
+            e
+        ),
+    }
+}
+
 fn main() {
+    embed_sidecar_checksum();
     tauri_build::build()
 }
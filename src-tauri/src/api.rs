@@ -0,0 +1,330 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_http::reqwest;
+
+use crate::{http, settings};
+
+// GameX backend the launcher talks to unless configured otherwise
+pub const API_BASE_URL: &str = "http://localhost:3000";
+
+// Backend origin used by every API call. Seeded from GAMEX_API_URL when set,
+// otherwise from the api_base_url setting.
+pub struct ApiConfig {
+    base_url: RwLock<String>,
+}
+
+impl ApiConfig {
+    pub fn load(settings: &settings::Settings) -> Self {
+        let base_url = env::var("GAMEX_API_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| settings.api_base_url.clone());
+
+        println!("[Tauri] API base URL: {}", base_url);
+        Self {
+            base_url: RwLock::new(normalize_base_url(&base_url)),
+        }
+    }
+
+    pub fn get(&self) -> String {
+        self.base_url
+            .read()
+            .map(|url| url.clone())
+            .unwrap_or_else(|_| API_BASE_URL.to_string())
+    }
+
+    pub fn set(&self, base_url: &str) {
+        if let Ok(mut current) = self.base_url.write() {
+            *current = normalize_base_url(base_url);
+        }
+    }
+}
+
+fn normalize_base_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+pub fn base_url(app_handle: &AppHandle) -> String {
+    app_handle.state::<ApiConfig>().get()
+}
+
+#[tauri::command]
+pub fn api_base_url(app_handle: AppHandle) -> String {
+    base_url(&app_handle)
+}
+
+// Point the launcher at another backend (e.g. staging) and remember it
+#[tauri::command]
+pub fn set_api_base_url(url: String, app_handle: AppHandle) -> Result<String, String> {
+    let url = normalize_base_url(&url);
+    settings::update(&app_handle, |settings| settings.api_base_url = url.clone())?;
+    Ok(base_url(&app_handle))
+}
+
+// Largest id list accepted by POST /games/batch
+pub const MAX_BATCH_SIZE: usize = 100;
+
+// MongoDB ObjectId as serialized by the API (`{ "$oid": "..." }`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiObjectId {
+    #[serde(rename = "$oid")]
+    pub oid: String,
+}
+
+// How an executable is started, mirroring the API's `launch_kind`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchKind {
+    #[default]
+    Native,
+    Uri,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiExecutable {
+    pub platform: String,
+    pub url: String,
+    #[serde(default)]
+    pub launch_kind: LaunchKind,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiGame {
+    #[serde(rename = "_id")]
+    pub id: Option<ApiObjectId>,
+    pub name: String,
+    #[serde(default)]
+    pub executables: Vec<ApiExecutable>,
+}
+
+impl ApiGame {
+    pub fn executable_for(&self, platform: &str) -> Option<&ApiExecutable> {
+        self.executables
+            .iter()
+            .find(|exec| exec.platform == platform)
+    }
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    games: Vec<ApiGame>,
+}
+
+// Fetch games by id, splitting into as many batch calls as needed
+pub async fn fetch_games_batch(
+    client: &reqwest::Client,
+    base_url: &str,
+    ids: &[String],
+) -> Result<Vec<ApiGame>, String> {
+    let mut games = Vec::new();
+
+    for chunk in ids.chunks(MAX_BATCH_SIZE) {
+        let body = serde_json::to_vec(&serde_json::json!({ "ids": chunk }))
+            .map_err(|e| format!("Failed to encode batch request: {}", e))?;
+
+        let response = client
+            .post(format!("{}/games/batch", base_url))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach API: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("API batch request failed: {}", response.status()));
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read API response: {}", e))?;
+        let batch: BatchResponse =
+            serde_json::from_slice(&bytes).map_err(|e| format!("Invalid API response: {}", e))?;
+
+        games.extend(batch.games);
+    }
+
+    Ok(games)
+}
+
+// Register a game with the backend, returning the created record
+pub async fn create_game(
+    client: &reqwest::Client,
+    base_url: &str,
+    request: &serde_json::Value,
+) -> Result<ApiGame, String> {
+    let body = serde_json::to_vec(request)
+        .map_err(|e| format!("Failed to encode create request: {}", e))?;
+
+    let response = client
+        .post(format!("{}/games", base_url))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach API: {}", e))?;
+
+    let status = response.status();
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+
+    if !status.is_success() {
+        return Err(format!(
+            "API rejected game ({}): {}",
+            status,
+            String::from_utf8_lossy(&bytes)
+        ));
+    }
+
+    serde_json::from_slice(&bytes).map_err(|e| format!("Invalid API response: {}", e))
+}
+
+#[derive(Deserialize)]
+struct GatewaysResponse {
+    gateways: Vec<String>,
+}
+
+// Server-recommended public gateways, in preference order
+pub async fn fetch_gateways(
+    client: &reqwest::Client,
+    base_url: &str,
+) -> Result<Vec<String>, String> {
+    let response = client
+        .get(format!("{}/config/gateways", base_url))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach API: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("API gateway request failed: {}", response.status()));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+    let body: GatewaysResponse =
+        serde_json::from_slice(&bytes).map_err(|e| format!("Invalid API response: {}", e))?;
+
+    Ok(body.gateways)
+}
+
+#[derive(Deserialize)]
+struct VersionResponse {
+    min_client_version: String,
+}
+
+pub async fn fetch_min_client_version(
+    client: &reqwest::Client,
+    base_url: &str,
+) -> Result<String, String> {
+    let response = client
+        .get(format!("{}/version", base_url))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach API: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("API version request failed: {}", response.status()));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+    let body: VersionResponse =
+        serde_json::from_slice(&bytes).map_err(|e| format!("Invalid API response: {}", e))?;
+
+    Ok(body.min_client_version)
+}
+
+// Whether `client_version` is older than the API's minimum. Pre-release
+// builds of the minimum version count as older, following semver ordering.
+pub fn is_client_outdated(client_version: &str, min_client_version: &str) -> Result<bool, String> {
+    let client = semver::Version::parse(client_version.trim())
+        .map_err(|e| format!("Invalid client version {}: {}", client_version, e))?;
+    let min = semver::Version::parse(min_client_version.trim()).map_err(|e| {
+        format!(
+            "Invalid minimum client version {}: {}",
+            min_client_version, e
+        )
+    })?;
+    Ok(client < min)
+}
+
+#[derive(Clone, Serialize)]
+struct ClientOutdated {
+    client_version: String,
+    min_client_version: String,
+}
+
+// Startup check against GET /version; emits `client-outdated` when this
+// launcher is too old for the API. An unreachable API is not an error here.
+pub async fn check_client_version(app_handle: &AppHandle) {
+    let client = http::client(app_handle);
+    let min_client_version = match fetch_min_client_version(&client, &base_url(app_handle)).await {
+        Ok(version) => version,
+        Err(e) => {
+            eprintln!("[API] Skipping client version check: {}", e);
+            return;
+        }
+    };
+
+    let client_version = app_handle.package_info().version.to_string();
+    match is_client_outdated(&client_version, &min_client_version) {
+        Ok(true) => {
+            println!(
+                "[API] Launcher {} is older than the minimum supported {}",
+                client_version, min_client_version
+            );
+            let _ = app_handle.emit(
+                "client-outdated",
+                ClientOutdated {
+                    client_version,
+                    min_client_version,
+                },
+            );
+        }
+        Ok(false) => {}
+        Err(e) => eprintln!("[API] {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn older_clients_are_outdated() {
+        assert_eq!(is_client_outdated("0.1.0", "0.2.0"), Ok(true));
+        assert_eq!(is_client_outdated("0.9.9", "1.0.0"), Ok(true));
+        assert_eq!(is_client_outdated("1.2.3", "1.2.10"), Ok(true));
+    }
+
+    #[test]
+    fn current_and_newer_clients_are_not_outdated() {
+        assert_eq!(is_client_outdated("0.2.0", "0.2.0"), Ok(false));
+        assert_eq!(is_client_outdated("1.2.10", "1.2.3"), Ok(false));
+        assert_eq!(is_client_outdated(" 2.0.0\n", "1.9.0"), Ok(false));
+    }
+
+    #[test]
+    fn pre_releases_of_the_minimum_are_outdated() {
+        assert_eq!(is_client_outdated("1.0.0-beta.1", "1.0.0"), Ok(true));
+        assert_eq!(is_client_outdated("1.0.0", "1.0.0-beta.1"), Ok(false));
+    }
+
+    #[test]
+    fn unparseable_versions_are_errors() {
+        assert!(is_client_outdated("1.0", "1.0.0").is_err());
+        assert!(is_client_outdated("1.0.0", "latest").is_err());
+    }
+}
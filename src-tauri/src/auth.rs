@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::redact;
+
+// Prefix identifying download-ownership messages and their format version
+const DOWNLOAD_MESSAGE_PREFIX: &str = "gamex-download:v1";
+// Authorization scheme used for signed download requests
+const DOWNLOAD_AUTH_SCHEME: &str = "GameX-Download";
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+static NONCE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadRequest {
+    pub wallet: String,
+    pub game_id: String,
+    pub nonce: String,
+    pub issued_at: u64,
+    // Exact message the wallet must sign
+    pub message: String,
+}
+
+// Unique per-request nonce; uniqueness matters here, not secrecy
+fn make_nonce(wallet: &str, game_id: &str, issued_at_nanos: u128) -> String {
+    let counter = NONCE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut hasher = Sha256::new();
+    hasher.update(wallet.as_bytes());
+    hasher.update(game_id.as_bytes());
+    hasher.update(issued_at_nanos.to_le_bytes());
+    hasher.update(counter.to_le_bytes());
+    format!("{:x}", hasher.finalize())[..32].to_string()
+}
+
+pub fn download_message(wallet: &str, game_id: &str, nonce: &str, issued_at: u64) -> String {
+    format!(
+        "{}:{}:{}:{}:{}",
+        DOWNLOAD_MESSAGE_PREFIX, wallet, game_id, nonce, issued_at
+    )
+}
+
+// Build the message a wallet signs to prove it may download a game
+#[tauri::command]
+pub fn make_download_request(wallet: String, game_id: String) -> Result<DownloadRequest, String> {
+    if wallet.is_empty() || game_id.is_empty() {
+        return Err("Wallet and game id are required".to_string());
+    }
+    if wallet.contains(':') || game_id.contains(':') {
+        return Err("Wallet and game id must not contain ':'".to_string());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("System clock error: {}", e))?;
+    let issued_at = now.as_secs();
+    let nonce = make_nonce(&wallet, &game_id, now.as_nanos());
+    let message = download_message(&wallet, &game_id, &nonce, issued_at);
+    println!(
+        "[Auth] Download request for game {} by {}",
+        game_id,
+        redact::address(&wallet)
+    );
+
+    Ok(DownloadRequest {
+        wallet,
+        game_id,
+        nonce,
+        issued_at,
+        message,
+    })
+}
+
+// Authorization header value carrying a signed download request
+#[tauri::command]
+pub fn attach_signature(req: DownloadRequest, signature: String) -> Result<String, String> {
+    if signature.is_empty() || !signature.chars().all(|c| BASE58_ALPHABET.contains(c)) {
+        return Err("Signature must be base58 encoded".to_string());
+    }
+
+    if req.message != download_message(&req.wallet, &req.game_id, &req.nonce, req.issued_at) {
+        return Err("Download request message does not match its fields".to_string());
+    }
+
+    Ok(format!(
+        "{} wallet={},game={},nonce={},ts={},sig={}",
+        DOWNLOAD_AUTH_SCHEME, req.wallet, req.game_id, req.nonce, req.issued_at, signature
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET: &str = "4Nd1mYQzGmE5UvWvKzR3Hhx8oVy7nDdJqKzw2XTvYQzu";
+    const GAME_ID: &str = "665f1c2ab9d3e4f5a6b7c8d9";
+
+    #[test]
+    fn message_is_colon_separated_behind_a_versioned_prefix() {
+        assert_eq!(
+            download_message(WALLET, GAME_ID, "abc123", 1_700_000_000),
+            format!("gamex-download:v1:{}:{}:abc123:1700000000", WALLET, GAME_ID)
+        );
+    }
+
+    #[test]
+    fn requests_carry_a_fresh_nonce_and_matching_message() {
+        let first = make_download_request(WALLET.to_string(), GAME_ID.to_string()).unwrap();
+        let second = make_download_request(WALLET.to_string(), GAME_ID.to_string()).unwrap();
+
+        assert_eq!(first.nonce.len(), 32);
+        assert!(first.nonce.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first.nonce, second.nonce);
+        assert_eq!(
+            first.message,
+            download_message(WALLET, GAME_ID, &first.nonce, first.issued_at)
+        );
+    }
+
+    #[test]
+    fn requests_need_plain_wallet_and_game_id() {
+        assert!(make_download_request(String::new(), GAME_ID.to_string()).is_err());
+        assert!(make_download_request(WALLET.to_string(), String::new()).is_err());
+        // A ':' would shift the fields of the signed message
+        assert!(make_download_request(format!("{}:x", WALLET), GAME_ID.to_string()).is_err());
+    }
+
+    #[test]
+    fn signature_header_lists_every_field() {
+        let req = DownloadRequest {
+            wallet: WALLET.to_string(),
+            game_id: GAME_ID.to_string(),
+            nonce: "abc123".to_string(),
+            issued_at: 1_700_000_000,
+            message: download_message(WALLET, GAME_ID, "abc123", 1_700_000_000),
+        };
+        assert_eq!(
+            attach_signature(req, "5sig".to_string()).unwrap(),
+            format!(
+                "GameX-Download wallet={},game={},nonce=abc123,ts=1700000000,sig=5sig",
+                WALLET, GAME_ID
+            )
+        );
+    }
+
+    #[test]
+    fn signature_must_be_base58_over_an_untampered_message() {
+        let req = make_download_request(WALLET.to_string(), GAME_ID.to_string()).unwrap();
+        assert!(attach_signature(req.clone(), String::new()).is_err());
+        // 0, O, I and l aren't in the base58 alphabet
+        assert!(attach_signature(req.clone(), "0OIl".to_string()).is_err());
+        assert!(attach_signature(req.clone(), "sig+/=".to_string()).is_err());
+
+        let tampered = DownloadRequest {
+            game_id: "someothergame".to_string(),
+            ..req
+        };
+        assert!(attach_signature(tampered, "5sig".to_string()).is_err());
+    }
+}
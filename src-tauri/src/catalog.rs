@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use tokio::sync::Mutex;
+
+use crate::{api, http};
+
+// Calls within this window reuse the last result instead of hitting the API
+const SYNC_DEBOUNCE: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Serialize)]
+pub struct SyncResult {
+    pub revision: u64,
+    pub changed: bool,
+    // Only games changed since the caller's revision rather than the whole catalog
+    pub incremental: bool,
+    // Full game records as returned by the API; empty when nothing changed
+    pub games: Vec<serde_json::Value>,
+}
+
+struct LastSync {
+    at: Instant,
+    since_revision: Option<u64>,
+    result: SyncResult,
+}
+
+// Serializes syncs so concurrent callers wait for the one in flight and then
+// share its result
+#[derive(Default)]
+pub struct CatalogSync {
+    last: Mutex<Option<LastSync>>,
+    // Revision of the last pull and the newest updated_at it contained, so a
+    // caller at that revision only needs the games written after it
+    cursor: Mutex<Option<(u64, String)>>,
+}
+
+// Newest `updated_at` among pulled games (fixed-width RFC 3339, so string
+// order is chronological)
+fn newest_update(games: &[serde_json::Value]) -> Option<String> {
+    games
+        .iter()
+        .filter_map(|game| game.get("updated_at")?.as_str())
+        .max()
+        .map(str::to_string)
+}
+
+#[derive(Deserialize)]
+struct RevisionResponse {
+    revision: u64,
+}
+
+async fn fetch_revision(client: &reqwest::Client, base_url: &str) -> Result<u64, String> {
+    let response = client
+        .get(format!("{}/catalog/revision", base_url))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach API: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "API revision request failed: {}",
+            response.status()
+        ));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+    let body: RevisionResponse =
+        serde_json::from_slice(&bytes).map_err(|e| format!("Invalid API response: {}", e))?;
+
+    Ok(body.revision)
+}
+
+async fn fetch_games(
+    client: &reqwest::Client,
+    base_url: &str,
+    updated_after: Option<&str>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut request = client.get(format!("{}/games", base_url));
+    if let Some(updated_after) = updated_after {
+        request = request.query(&[("updated_after", updated_after)]);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach API: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("API games request failed: {}", response.status()));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Invalid API response: {}", e))
+}
+
+// Refresh the local catalog cache. Only the revision is fetched when it still
+// matches `since_revision`; otherwise the games are pulled as well, just the
+// changed ones when `since_revision` is the revision of our last pull.
+#[tauri::command]
+pub async fn sync_catalog(
+    since_revision: Option<u64>,
+    app_handle: AppHandle,
+) -> Result<SyncResult, String> {
+    let state = app_handle.state::<CatalogSync>();
+    let mut last = state.last.lock().await;
+
+    if let Some(last) = last.as_ref() {
+        if last.since_revision == since_revision && last.at.elapsed() < SYNC_DEBOUNCE {
+            return Ok(last.result.clone());
+        }
+    }
+
+    let client = http::client(&app_handle);
+    let base_url = api::base_url(&app_handle);
+    let revision = fetch_revision(&client, &base_url).await?;
+
+    let result = if Some(revision) == since_revision {
+        SyncResult {
+            revision,
+            changed: false,
+            incremental: false,
+            games: Vec::new(),
+        }
+    } else {
+        let mut cursor = state.cursor.lock().await;
+        let updated_after = cursor
+            .as_ref()
+            .filter(|(pulled, _)| Some(*pulled) == since_revision)
+            .map(|(_, updated_at)| updated_at.clone());
+
+        let games = fetch_games(&client, &base_url, updated_after.as_deref()).await?;
+        println!(
+            "[Catalog] Revision {:?} -> {}, pulled {} games{}",
+            since_revision,
+            revision,
+            games.len(),
+            if updated_after.is_some() {
+                " (incremental)"
+            } else {
+                ""
+            }
+        );
+
+        let newest = newest_update(&games)
+            .into_iter()
+            .chain(updated_after.clone())
+            .max();
+        *cursor = newest.map(|updated_at| (revision, updated_at));
+
+        SyncResult {
+            revision,
+            changed: true,
+            incremental: updated_after.is_some(),
+            games,
+        }
+    };
+
+    *last = Some(LastSync {
+        at: Instant::now(),
+        since_revision,
+        result: result.clone(),
+    });
+    Ok(result)
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+// Registered in tauri.conf.json; storefront pages link to gamex://<action>/<game_id>
+pub const SCHEME: &str = "gamex";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeepLinkAction {
+    Install,
+    Play,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepLink {
+    pub action: DeepLinkAction,
+    pub game_id: String,
+    pub url: String,
+}
+
+// Links that arrive before the frontend subscribes to `deep-link` events
+// (e.g. the one that launched the app) are kept until it takes them
+#[derive(Default)]
+pub struct PendingDeepLink {
+    link: Mutex<Option<DeepLink>>,
+    taken: AtomicBool,
+}
+
+// Parse gamex://install/<game_id> or gamex://play/<game_id>. The host is the
+// action; a trailing slash or query string is ignored.
+pub fn parse(url: &str) -> Result<DeepLink, String> {
+    let rest = url
+        .split_once("://")
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+        .map(|(_, rest)| rest)
+        .ok_or_else(|| format!("Not a {}:// link: {}", SCHEME, url))?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+
+    let mut segments = rest.split('/').filter(|segment| !segment.is_empty());
+    let action = match segments.next().map(str::to_ascii_lowercase).as_deref() {
+        Some("install") => DeepLinkAction::Install,
+        Some("play") => DeepLinkAction::Play,
+        _ => return Err(format!("Unknown deep link action: {}", url)),
+    };
+    let game_id = match (segments.next(), segments.next()) {
+        (Some(game_id), None) if game_id.chars().all(|c| c.is_ascii_alphanumeric()) => game_id,
+        _ => return Err(format!("Invalid game id in deep link: {}", url)),
+    };
+
+    Ok(DeepLink {
+        action,
+        game_id: game_id.to_string(),
+        url: url.to_string(),
+    })
+}
+
+fn parse_logged(url: &str) -> Option<DeepLink> {
+    parse(url).map_err(|e| eprintln!("[DeepLink] {}", e)).ok()
+}
+
+// Forward links to the frontend as `deep-link` events, keeping the last one
+// for `take_pending_deep_link` until the frontend has asked for it once
+pub fn handle_urls<I, S>(app_handle: &AppHandle, urls: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let pending = app_handle.state::<PendingDeepLink>();
+    for link in urls
+        .into_iter()
+        .filter_map(|url| parse_logged(url.as_ref()))
+    {
+        println!("[DeepLink] {:?} game {}", link.action, link.game_id);
+        if !pending.taken.load(Ordering::SeqCst) {
+            if let Ok(mut slot) = pending.link.lock() {
+                *slot = Some(link.clone());
+            }
+        }
+        let _ = app_handle.emit("deep-link", link);
+    }
+}
+
+// Bring the existing window forward when a second launch forwards a link
+pub fn focus_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+// The link the app was opened with, if any. Call once the frontend listens
+// for `deep-link`; later links only arrive as events.
+#[tauri::command]
+pub fn take_pending_deep_link(app_handle: AppHandle) -> Option<DeepLink> {
+    let pending = app_handle.state::<PendingDeepLink>();
+    pending.taken.store(true, Ordering::SeqCst);
+    pending.link.lock().ok().and_then(|mut link| link.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME_ID: &str = "65e1c0ffee0000000000beef";
+
+    #[test]
+    fn install_and_play_links_parse() {
+        let link = parse(&format!("gamex://install/{}", GAME_ID)).unwrap();
+        assert_eq!(link.action, DeepLinkAction::Install);
+        assert_eq!(link.game_id, GAME_ID);
+
+        let url = format!("GameX://PLAY/{}", GAME_ID);
+        let link = parse(&url).unwrap();
+        assert_eq!(link.action, DeepLinkAction::Play);
+        assert_eq!(link.url, url);
+    }
+
+    #[test]
+    fn trailing_slashes_and_queries_are_ignored() {
+        for url in [
+            format!("gamex://play/{}/", GAME_ID),
+            format!("gamex://play/{}?source=store", GAME_ID),
+            format!("gamex://play/{}#reviews", GAME_ID),
+        ] {
+            assert_eq!(parse(&url).unwrap().game_id, GAME_ID, "{}", url);
+        }
+    }
+
+    #[test]
+    fn other_schemes_and_actions_are_rejected() {
+        assert!(parse(&format!("https://play/{}", GAME_ID)).is_err());
+        assert!(parse(&format!("gamex:/play/{}", GAME_ID)).is_err());
+        assert!(parse(&format!("gamex://uninstall/{}", GAME_ID)).is_err());
+        assert!(parse("gamex://").is_err());
+    }
+
+    #[test]
+    fn game_ids_must_be_a_single_alphanumeric_segment() {
+        assert!(parse("gamex://install").is_err());
+        assert!(parse(&format!("gamex://install/{}/extra", GAME_ID)).is_err());
+        assert!(parse("gamex://install/..%2Fsettings").is_err());
+        assert!(parse("gamex://play/game-1").is_err());
+    }
+
+    #[test]
+    fn actions_serialize_lowercase() {
+        let link = parse(&format!("gamex://install/{}", GAME_ID)).unwrap();
+        let json = serde_json::to_value(link).unwrap();
+        assert_eq!(json["action"], "install");
+        assert_eq!(json["game_id"], GAME_ID);
+    }
+}
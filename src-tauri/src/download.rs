@@ -0,0 +1,637 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_http::reqwest;
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use crate::{http, integrity, ipfs, settings, ProcessManager};
+
+// Bytes requested per `cat` call
+pub(crate) const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+// Consecutive failed chunks tolerated before giving up
+const MAX_CHUNK_ATTEMPTS: u32 = 3;
+// Wait before retrying a failed chunk, doubled on each further failure
+const CHUNK_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+// Download speed is averaged over roughly this much recent history
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum InstallPhase {
+    Download,
+    Verify,
+    Pin,
+    Finalize,
+}
+
+#[derive(Clone, Serialize)]
+struct InstallProgress {
+    cid: String,
+    phase: InstallPhase,
+    bytes: u64,
+    total: u64,
+    // Zero outside the download phase
+    bytes_per_sec: u64,
+    // None until a speed is known, and outside the download phase
+    eta_secs: Option<u64>,
+}
+
+// Emitted when a `paranoid` re-read of the finished file doesn't match
+#[derive(Clone, Serialize)]
+struct VerifyFailed {
+    cid: String,
+    path: String,
+    expected_sha256: String,
+    actual_sha256: String,
+}
+
+// Sliding window of (time, bytes so far) samples for a smoothed speed that
+// doesn't jump with every chunk
+struct SpeedWindow {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl SpeedWindow {
+    fn new(start_bytes: u64) -> Self {
+        Self::starting_at(Instant::now(), start_bytes)
+    }
+
+    fn starting_at(at: Instant, start_bytes: u64) -> Self {
+        SpeedWindow {
+            samples: VecDeque::from([(at, start_bytes)]),
+        }
+    }
+
+    fn record(&mut self, bytes: u64) {
+        self.record_at(Instant::now(), bytes);
+    }
+
+    fn record_at(&mut self, now: Instant, bytes: u64) {
+        self.samples.push_back((now, bytes));
+        // Keep one sample older than the window so the span covers all of it
+        while self.samples.len() > 2
+            && self
+                .samples
+                .get(1)
+                .is_some_and(|(at, _)| now.duration_since(*at) >= SPEED_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn bytes_per_sec(&self) -> u64 {
+        let (Some((first_at, first_bytes)), Some((last_at, last_bytes))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0;
+        };
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return 0;
+        }
+        (last_bytes.saturating_sub(*first_bytes) as f64 / elapsed) as u64
+    }
+}
+
+fn emit_progress(app_handle: &AppHandle, progress: InstallProgress) {
+    let _ = app_handle.emit("install-progress", progress);
+}
+
+// Progress for a phase without a transfer rate (verify, pin, finalize)
+fn phase_progress(cid: &str, phase: InstallPhase, total: u64) -> InstallProgress {
+    InstallProgress {
+        cid: cid.to_string(),
+        phase,
+        bytes: total,
+        total,
+        bytes_per_sec: 0,
+        eta_secs: None,
+    }
+}
+
+// Download-phase progress; the ETA rounds up so it only reads 0 when done
+fn download_progress(cid: &str, bytes: u64, total: u64, speed: &SpeedWindow) -> InstallProgress {
+    let bytes_per_sec = speed.bytes_per_sec();
+    InstallProgress {
+        cid: cid.to_string(),
+        phase: InstallPhase::Download,
+        bytes,
+        total,
+        bytes_per_sec,
+        eta_secs: (bytes_per_sec > 0).then(|| total.saturating_sub(bytes).div_ceil(bytes_per_sec)),
+    }
+}
+
+// `path` with `suffix` appended to its file name, in the same directory
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn part_path(dest: &Path) -> PathBuf {
+    with_suffix(dest, ".part")
+}
+
+// Records which CID a `.part` file holds, so a leftover from another build
+// at the same destination is never appended to
+fn part_cid_path(dest: &Path) -> PathBuf {
+    with_suffix(dest, ".part.cid")
+}
+
+// Byte to resume from given what's on disk: the part file's length when it
+// belongs to this CID and isn't larger than the content, otherwise 0
+fn resume_offset(part_len: Option<u64>, part_cid: Option<&str>, cid: &str, total: u64) -> u64 {
+    match (part_len, part_cid) {
+        (Some(len), Some(part_cid)) if part_cid.trim() == cid && len <= total => len,
+        _ => 0,
+    }
+}
+
+fn chunk_retry_delay(attempt: u32) -> Duration {
+    CHUNK_RETRY_DELAY * 2u32.pow(attempt.saturating_sub(1).min(4))
+}
+
+// Total size of the file behind a CID, from `files/stat`
+pub(crate) async fn cid_size(
+    client: &reqwest::Client,
+    api_url: &str,
+    cid: &str,
+) -> Result<u64, String> {
+    let url = format!("{}/api/v0/files/stat?arg=/ipfs/{}", api_url, cid);
+    let response = client
+        .post(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to stat CID {}: {}", cid, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to stat CID {}: {}", cid, response.status()));
+    }
+
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read stat response: {}", e))?;
+    let stat: serde_json::Value =
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid stat response: {}", e))?;
+
+    stat.get("Size")
+        .and_then(|size| size.as_u64())
+        .ok_or_else(|| format!("Stat response for {} has no size", cid))
+}
+
+pub(crate) async fn fetch_chunk(
+    client: &reqwest::Client,
+    api_url: &str,
+    cid: &str,
+    offset: u64,
+    length: u64,
+    authorization: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let url = format!(
+        "{}/api/v0/cat?arg={}&offset={}&length={}",
+        api_url, cid, offset, length
+    );
+    let mut request = client.post(&url);
+    if let Some(authorization) = authorization {
+        request = request.header("Authorization", authorization);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch chunk at {}: {}", offset, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to fetch chunk at {}: {}",
+            offset,
+            response.status()
+        ));
+    }
+
+    response
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| format!("Failed to read chunk at {}: {}", offset, e))
+}
+
+// Download a CID to `dest` in chunks through a `.part` file, resuming from
+// whatever a previous attempt already wrote. `authorization` is the signed
+// header value from `attach_signature`, forwarded with each chunk request.
+// The download is checked against `expected_sha256` and pinned when asked,
+// all reported through `install-progress` events. `paranoid` additionally
+// re-reads the file once it's in place and deletes it with a `verify-failed`
+// event if what's on disk no longer matches the checksum.
+#[tauri::command]
+pub async fn download_cid(
+    cid: String,
+    dest: String,
+    authorization: Option<String>,
+    expected_sha256: Option<String>,
+    pin: Option<bool>,
+    paranoid: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let paranoid = paranoid.unwrap_or(false);
+    if paranoid && expected_sha256.is_none() {
+        return Err("A paranoid download needs an expected checksum".to_string());
+    }
+
+    app_handle
+        .state::<ProcessManager>()
+        .ensure_ipfs_available()?;
+
+    let dest = PathBuf::from(dest);
+    let part = part_path(&dest);
+    let part_cid = part_cid_path(&dest);
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create download directory: {}", e))?;
+    }
+
+    let client = http::client(&app_handle);
+    let api_url = settings::current(&app_handle).ipfs_api_url();
+    let total = cid_size(&client, &api_url, &cid).await?;
+
+    let part_len = fs::metadata(&part)
+        .await
+        .ok()
+        .map(|metadata| metadata.len());
+    let recorded_cid = fs::read_to_string(&part_cid).await.ok();
+    let mut offset = resume_offset(part_len, recorded_cid.as_deref(), &cid, total);
+
+    // A part file of another CID, or larger than the content, can't be
+    // trusted; start over
+    if offset == 0 && part_len.is_some_and(|len| len > 0) {
+        println!("[Download] Discarding stale partial download {:?}", part);
+        fs::remove_file(&part)
+            .await
+            .map_err(|e| format!("Failed to reset partial download: {}", e))?;
+    }
+    fs::write(&part_cid, &cid)
+        .await
+        .map_err(|e| format!("Failed to record partial download: {}", e))?;
+
+    if offset > 0 {
+        println!(
+            "[Download] Resuming {} from byte {} of {}",
+            cid, offset, total
+        );
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&part)
+        .await
+        .map_err(|e| format!("Failed to open partial download: {}", e))?;
+
+    let mut speed = SpeedWindow::new(offset);
+    let mut attempts = 0;
+    while offset < total {
+        let length = CHUNK_SIZE.min(total - offset);
+
+        let chunk = match fetch_chunk(
+            &client,
+            &api_url,
+            &cid,
+            offset,
+            length,
+            authorization.as_deref(),
+        )
+        .await
+        {
+            Ok(chunk) if !chunk.is_empty() => chunk,
+            Ok(_) => return Err(format!("Empty chunk at {}", offset)),
+            Err(e) => {
+                attempts += 1;
+                eprintln!(
+                    "[Download] Chunk failed ({}/{}): {}",
+                    attempts, MAX_CHUNK_ATTEMPTS, e
+                );
+                if attempts >= MAX_CHUNK_ATTEMPTS {
+                    return Err(e);
+                }
+                tokio::time::sleep(chunk_retry_delay(attempts)).await;
+                continue;
+            }
+        };
+        attempts = 0;
+
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write partial download: {}", e))?;
+        offset += chunk.len() as u64;
+
+        speed.record(offset);
+        emit_progress(&app_handle, download_progress(&cid, offset, total, &speed));
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush download: {}", e))?;
+    if paranoid {
+        // Make the rename-time re-read come from storage as far as we can
+        file.sync_all()
+            .await
+            .map_err(|e| format!("Failed to sync download: {}", e))?;
+    }
+    drop(file);
+
+    if let Some(expected) = &expected_sha256 {
+        emit_progress(
+            &app_handle,
+            phase_progress(&cid, InstallPhase::Verify, total),
+        );
+        if !integrity::sha256_matches(part.clone(), expected).await? {
+            // A corrupt part file would otherwise be resumed from next time
+            let _ = fs::remove_file(&part).await;
+            let _ = fs::remove_file(&part_cid).await;
+            return Err(format!("Checksum mismatch for {}", cid));
+        }
+    }
+
+    if pin.unwrap_or(false) {
+        emit_progress(&app_handle, phase_progress(&cid, InstallPhase::Pin, total));
+        ipfs::pin_cid(cid.clone(), app_handle.clone()).await?;
+    }
+
+    emit_progress(
+        &app_handle,
+        phase_progress(&cid, InstallPhase::Finalize, total),
+    );
+    fs::rename(&part, &dest)
+        .await
+        .map_err(|e| format!("Failed to finalize download: {}", e))?;
+    let _ = fs::remove_file(&part_cid).await;
+
+    if let Some(expected) = expected_sha256.filter(|_| paranoid) {
+        let actual = integrity::sha256_file_async(dest.clone()).await?;
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            eprintln!(
+                "[Download] {} changed on disk after verification: {:?}",
+                cid, dest
+            );
+            let _ = fs::remove_file(&dest).await;
+            let _ = app_handle.emit(
+                "verify-failed",
+                VerifyFailed {
+                    cid: cid.clone(),
+                    path: dest.to_string_lossy().to_string(),
+                    expected_sha256: expected,
+                    actual_sha256: actual,
+                },
+            );
+            return Err(format!("On-disk checksum mismatch for {}", cid));
+        }
+    }
+
+    println!("[Download] Completed {} -> {:?}", cid, dest);
+    Ok(dest.to_string_lossy().to_string())
+}
+
+// Create the subfolders a game's manifest says must exist on first run
+// (saves, config, ...) relative to `install_dir`. Every path is checked
+// before anything is created; returns the ones that didn't exist yet.
+#[tauri::command]
+pub async fn create_install_dirs(
+    install_dir: String,
+    create_dirs: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let root = PathBuf::from(&install_dir);
+    if !root.is_dir() {
+        return Err(format!("Install directory not found: {}", install_dir));
+    }
+
+    if let Some(invalid) = create_dirs
+        .iter()
+        .find(|dir| dir.trim().is_empty() || !integrity::stays_inside(Path::new(dir)))
+    {
+        return Err(format!(
+            "Directory must be a path inside the install dir: {}",
+            invalid
+        ));
+    }
+
+    let mut created = Vec::new();
+    for dir in create_dirs {
+        let path = root.join(&dir);
+        if path.is_dir() {
+            continue;
+        }
+        fs::create_dir_all(&path)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+        created.push(dir);
+    }
+
+    if !created.is_empty() {
+        println!(
+            "[Download] Created {} dir(s) in {}",
+            created.len(),
+            install_dir
+        );
+    }
+    Ok(created)
+}
+
+// Replace an installed binary with a new build without ever leaving it
+// missing: the build is downloaded next to it and verified first, then the
+// old binary is moved to `.bak` and only deleted once the new one is in place
+#[tauri::command]
+pub async fn update_installed(
+    old_path: String,
+    new_cid: String,
+    expected_sha256: String,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let expected = expected_sha256.trim();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Expected checksum must be 64 hex characters".to_string());
+    }
+
+    let installed = PathBuf::from(&old_path);
+    if !installed.is_file() {
+        return Err(format!("Installed file not found: {}", old_path));
+    }
+
+    // Same directory as the install, so the renames below stay atomic
+    let staged = with_suffix(&installed, ".new");
+    let backup = with_suffix(&installed, ".bak");
+
+    // Verified before it's moved into place as the staged file
+    download_cid(
+        new_cid.clone(),
+        staged.to_string_lossy().to_string(),
+        None,
+        Some(expected.to_string()),
+        None,
+        None,
+        app_handle,
+    )
+    .await?;
+
+    // Keep the old binary's permissions (notably the executable bit)
+    let permissions = fs::metadata(&installed)
+        .await
+        .map_err(|e| format!("Failed to read installed file: {}", e))?
+        .permissions();
+    fs::set_permissions(&staged, permissions)
+        .await
+        .map_err(|e| format!("Failed to set permissions on update: {}", e))?;
+
+    fs::rename(&installed, &backup)
+        .await
+        .map_err(|e| format!("Failed to back up installed file: {}", e))?;
+
+    if let Err(e) = fs::rename(&staged, &installed).await {
+        if let Err(restore) = fs::rename(&backup, &installed).await {
+            eprintln!("[Download] Failed to restore {:?}: {}", backup, restore);
+        }
+        let _ = fs::remove_file(&staged).await;
+        return Err(format!("Failed to install update: {}", e));
+    }
+
+    if let Err(e) = fs::remove_file(&backup).await {
+        eprintln!("[Download] Failed to remove backup {:?}: {}", backup, e);
+    }
+
+    println!("[Download] Updated {} to {}", old_path, new_cid);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CID: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+    #[test]
+    fn part_files_sit_next_to_the_destination() {
+        let dest = Path::new("/games/demo/game.exe");
+        assert_eq!(part_path(dest), Path::new("/games/demo/game.exe.part"));
+        assert_eq!(
+            part_cid_path(dest),
+            Path::new("/games/demo/game.exe.part.cid")
+        );
+    }
+
+    #[test]
+    fn resumes_a_part_file_of_the_same_cid() {
+        assert_eq!(resume_offset(Some(4096), Some(CID), CID, 10_000), 4096);
+        // Written by fs::write, so no trailing newline is expected, but tolerate one
+        assert_eq!(
+            resume_offset(Some(4096), Some(&format!("{}\n", CID)), CID, 10_000),
+            4096
+        );
+        assert_eq!(resume_offset(Some(10_000), Some(CID), CID, 10_000), 10_000);
+    }
+
+    #[test]
+    fn restarts_a_part_file_it_cant_trust() {
+        // Nothing downloaded yet
+        assert_eq!(resume_offset(None, None, CID, 10_000), 0);
+        // Left by a different build at the same destination
+        assert_eq!(resume_offset(Some(4096), Some("QmOther"), CID, 10_000), 0);
+        // Written before part files were tagged with their CID
+        assert_eq!(resume_offset(Some(4096), None, CID, 10_000), 0);
+        // Larger than the content
+        assert_eq!(resume_offset(Some(20_000), Some(CID), CID, 10_000), 0);
+    }
+
+    #[test]
+    fn chunk_retries_back_off() {
+        assert_eq!(chunk_retry_delay(1), Duration::from_millis(500));
+        assert_eq!(chunk_retry_delay(2), Duration::from_millis(1000));
+        assert_eq!(chunk_retry_delay(3), Duration::from_millis(2000));
+        assert_eq!(chunk_retry_delay(50), Duration::from_millis(8000));
+    }
+
+    #[test]
+    fn speed_is_averaged_over_the_window() {
+        let start = Instant::now();
+        let mut speed = SpeedWindow::starting_at(start, 0);
+        assert_eq!(speed.bytes_per_sec(), 0);
+
+        for second in 1..=4 {
+            speed.record_at(start + Duration::from_secs(second), second * 1000);
+        }
+        assert_eq!(speed.bytes_per_sec(), 1000);
+    }
+
+    #[test]
+    fn old_samples_leave_the_window() {
+        let start = Instant::now();
+        // A burst long ago shouldn't inflate the current speed
+        let mut speed = SpeedWindow::starting_at(start, 0);
+        speed.record_at(start + Duration::from_secs(1), 1_000_000);
+        for second in 2..=20 {
+            speed.record_at(
+                start + Duration::from_secs(second),
+                1_000_000 + (second - 1) * 100,
+            );
+        }
+        let rate = speed.bytes_per_sec();
+        assert!((100..=120).contains(&rate), "rate was {}", rate);
+    }
+
+    #[test]
+    fn resuming_counts_only_new_bytes() {
+        let start = Instant::now();
+        let mut speed = SpeedWindow::starting_at(start, 50_000);
+        speed.record_at(start + Duration::from_secs(2), 54_000);
+        assert_eq!(speed.bytes_per_sec(), 2000);
+    }
+
+    #[test]
+    fn phase_progress_reports_the_whole_file() {
+        let progress = phase_progress(CID, InstallPhase::Verify, 1234);
+        assert_eq!((progress.bytes, progress.total), (1234, 1234));
+        assert_eq!(progress.bytes_per_sec, 0);
+        assert!(progress.eta_secs.is_none());
+    }
+
+    #[test]
+    fn stalled_downloads_slow_to_zero() {
+        let start = Instant::now();
+        let mut speed = SpeedWindow::starting_at(start, 0);
+        speed.record_at(start + Duration::from_secs(1), 5000);
+        for second in 2..=10 {
+            speed.record_at(start + Duration::from_secs(second), 5000);
+        }
+        assert_eq!(speed.bytes_per_sec(), 0);
+        assert!(download_progress(CID, 5000, 10_000, &speed)
+            .eta_secs
+            .is_none());
+    }
+
+    #[test]
+    fn eta_rounds_up_the_remaining_time() {
+        let start = Instant::now();
+        let mut speed = SpeedWindow::starting_at(start, 0);
+        speed.record_at(start + Duration::from_secs(2), 2000);
+
+        let progress = download_progress(CID, 2000, 4500, &speed);
+        assert_eq!(progress.bytes_per_sec, 1000);
+        assert_eq!(progress.eta_secs, Some(3));
+        assert_eq!(download_progress(CID, 4500, 4500, &speed).eta_secs, Some(0));
+    }
+
+    #[test]
+    fn progress_serializes_with_lowercase_phases() {
+        let progress =
+            serde_json::to_value(phase_progress(CID, InstallPhase::Finalize, 10)).unwrap();
+        assert_eq!(progress["phase"], "finalize");
+        assert_eq!(progress["eta_secs"], serde_json::Value::Null);
+        let download = serde_json::to_value(InstallPhase::Download).unwrap();
+        assert_eq!(download, "download");
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+const HISTORY_FILE: &str = "launch_history.json";
+// Oldest entries are rotated out past this many launches
+const MAX_HISTORY_ENTRIES: usize = 200;
+
+// Serializes read-modify-write cycles on the history file
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchRecord {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+    // Unix timestamp in seconds
+    pub launched_at: u64,
+}
+
+fn history_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(app_data_dir.join(HISTORY_FILE))
+}
+
+// Stored oldest first; a missing or corrupt file reads as empty
+fn read_history(path: &PathBuf) -> Vec<LaunchRecord> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn record_launch(
+    app_handle: &AppHandle,
+    path: &str,
+    game_id: Option<String>,
+) -> Result<(), String> {
+    let _guard = HISTORY_LOCK
+        .lock()
+        .map_err(|_| "Launch history lock poisoned".to_string())?;
+    let history_file = history_path(app_handle)?;
+
+    let mut history = read_history(&history_file);
+    history.push(LaunchRecord {
+        path: path.to_string(),
+        game_id,
+        launched_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0),
+    });
+    if history.len() > MAX_HISTORY_ENTRIES {
+        history.drain(..history.len() - MAX_HISTORY_ENTRIES);
+    }
+
+    if let Some(parent) = history_file.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    }
+    let json = serde_json::to_string(&history)
+        .map_err(|e| format!("Failed to serialize launch history: {}", e))?;
+
+    // Write then rename so a crash can't leave a truncated file behind
+    let tmp = history_file.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Failed to write launch history: {}", e))?;
+    std::fs::rename(&tmp, &history_file)
+        .map_err(|e| format!("Failed to save launch history: {}", e))
+}
+
+// Most recent launches first
+#[tauri::command]
+pub fn launch_history(limit: usize, app_handle: AppHandle) -> Result<Vec<LaunchRecord>, String> {
+    let _guard = HISTORY_LOCK
+        .lock()
+        .map_err(|_| "Launch history lock poisoned".to_string())?;
+    let history = read_history(&history_path(&app_handle)?);
+    Ok(history.into_iter().rev().take(limit).collect())
+}
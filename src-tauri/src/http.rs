@@ -0,0 +1,120 @@
+use std::env;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 60;
+// Per read through the gamex-ipfs:// proxy; each read is at most one chunk
+const DEFAULT_PROXY_TIMEOUT_SECS: u64 = 30;
+
+// Shared outbound HTTP clients, so every call gets the same timeouts
+pub struct HttpClient {
+    client: reqwest::Client,
+    proxy: reqwest::Client,
+}
+
+fn timeout_from_env(key: &str, default_secs: u64) -> Duration {
+    let secs = env::var(key)
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default_secs);
+    Duration::from_secs(secs)
+}
+
+fn build_client(connect_timeout: Duration, timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(connect_timeout)
+        .timeout(timeout)
+        .build()
+        .unwrap_or_else(|e| {
+            eprintln!("[Tauri] Failed to build HTTP client, using defaults: {}", e);
+            reqwest::Client::new()
+        })
+}
+
+impl HttpClient {
+    // Timeouts come from GAMEX_HTTP_CONNECT_TIMEOUT_SECS / GAMEX_HTTP_TIMEOUT_SECS,
+    // and GAMEX_PROXY_TIMEOUT_SECS for proxy reads
+    pub fn from_env() -> Self {
+        let connect_timeout = timeout_from_env(
+            "GAMEX_HTTP_CONNECT_TIMEOUT_SECS",
+            DEFAULT_CONNECT_TIMEOUT_SECS,
+        );
+        let timeout = timeout_from_env("GAMEX_HTTP_TIMEOUT_SECS", DEFAULT_REQUEST_TIMEOUT_SECS);
+        let proxy_timeout =
+            timeout_from_env("GAMEX_PROXY_TIMEOUT_SECS", DEFAULT_PROXY_TIMEOUT_SECS);
+
+        println!(
+            "[Tauri] HTTP client timeouts: connect {:?}, request {:?}, proxy {:?}",
+            connect_timeout, timeout, proxy_timeout
+        );
+        Self {
+            client: build_client(connect_timeout, timeout),
+            proxy: build_client(connect_timeout, proxy_timeout),
+        }
+    }
+}
+
+// Clone of the shared client (cheap, clients are reference counted)
+pub fn client(app_handle: &AppHandle) -> reqwest::Client {
+    app_handle.state::<HttpClient>().client.clone()
+}
+
+// Client for gamex-ipfs:// proxy reads, which have their own timeout
+pub fn proxy_client(app_handle: &AppHandle) -> reqwest::Client {
+    app_handle.state::<HttpClient>().proxy.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn timeouts_fall_back_to_the_default() {
+        let key = "GAMEX_TEST_TIMEOUT_SECS";
+        assert_eq!(timeout_from_env(key, 5), Duration::from_secs(5));
+        for invalid in ["", "0", "-3", "soon"] {
+            env::set_var(key, invalid);
+            assert_eq!(
+                timeout_from_env(key, 5),
+                Duration::from_secs(5),
+                "{:?}",
+                invalid
+            );
+        }
+        env::set_var(key, "12");
+        assert_eq!(timeout_from_env(key, 5), Duration::from_secs(12));
+        env::remove_var(key);
+    }
+
+    #[test]
+    fn slow_upstreams_time_out() {
+        // Accepts the connection and reads the request, but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            thread::sleep(Duration::from_secs(5));
+        });
+
+        let started = Instant::now();
+        let result = tauri::async_runtime::block_on(async move {
+            build_client(Duration::from_secs(1), Duration::from_millis(200))
+                .get(format!("http://{}/slow", address))
+                .send()
+                .await
+        });
+
+        let error = result.expect_err("a silent upstream should time out");
+        assert!(error.is_timeout(), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,200 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use tauri::{async_runtime, AppHandle};
+
+use crate::ipfs;
+
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+// SHA-256 of the IPFS sidecar this build was bundled with, embedded by build.rs
+const IPFS_SIDECAR_SHA256: Option<&str> = option_env!("IPFS_SIDECAR_SHA256");
+
+// Stream a file through SHA-256, returning the hex digest and the byte count
+pub fn sha256_file(path: &Path) -> Result<(String, u64), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    let mut size = 0u64;
+
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+
+    Ok((format!("{:x}", hasher.finalize()), size))
+}
+
+// Hash the file off the async runtime
+pub async fn sha256_file_async(path: PathBuf) -> Result<String, String> {
+    let (digest, _) = async_runtime::spawn_blocking(move || sha256_file(&path))
+        .await
+        .map_err(|e| format!("Checksum task failed: {}", e))??;
+    Ok(digest)
+}
+
+// Hash the file off the async runtime and compare it to the expected hex digest
+pub async fn sha256_matches(path: PathBuf, expected: &str) -> Result<bool, String> {
+    let actual = sha256_file_async(path).await?;
+    Ok(actual.eq_ignore_ascii_case(expected.trim()))
+}
+
+// Bundled sidecars are installed next to the launcher executable without
+// their target-triple suffix
+fn ipfs_sidecar_path() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate launcher executable: {}", e))?;
+    let dir = exe
+        .parent()
+        .ok_or_else(|| "Launcher executable has no parent directory".to_string())?;
+    Ok(dir.join(format!("ipfs{}", std::env::consts::EXE_SUFFIX)))
+}
+
+// Check the IPFS sidecar against the checksum baked in at build time. A
+// missing sidecar is left to the spawn, which reports it as unavailable.
+pub async fn verify_ipfs_sidecar() -> Result<(), String> {
+    let Some(expected) = IPFS_SIDECAR_SHA256 else {
+        println!("[Integrity] No IPFS sidecar checksum in this build, skipping check");
+        return Ok(());
+    };
+
+    let path = ipfs_sidecar_path()?;
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    if sha256_matches(path.clone(), expected).await? {
+        println!("[Integrity] IPFS sidecar checksum ok");
+        Ok(())
+    } else {
+        Err(format!("IPFS sidecar checksum mismatch: {:?}", path))
+    }
+}
+
+// Re-hash an installed file for "Verify files"; Ok(false) means it's corrupted
+#[tauri::command]
+pub async fn verify_installed(path: String, expected_sha256: String) -> Result<bool, String> {
+    let expected = expected_sha256.trim();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Expected checksum must be 64 hex characters".to_string());
+    }
+
+    let file = PathBuf::from(&path);
+    if !file.is_file() {
+        return Err(format!("Installed file not found: {}", path));
+    }
+
+    let matches = sha256_matches(file, expected).await?;
+    println!(
+        "[Integrity] Verified {}: {}",
+        path,
+        if matches { "ok" } else { "checksum mismatch" }
+    );
+    Ok(matches)
+}
+
+#[derive(Serialize)]
+pub struct InstallStatus {
+    pub installed: bool,
+    // Only true when a checksum was given and the entry matches it
+    pub verified: bool,
+}
+
+// A relative path that can't climb out of the directory it's joined onto
+pub fn stays_inside(relative: &Path) -> bool {
+    !relative.is_absolute()
+        && relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+// Per-card "Play" vs "Install" check: does `entry` (relative to
+// `install_dir`) exist, and does it match `expected_sha256` when given
+#[tauri::command]
+pub async fn is_installed(
+    install_dir: String,
+    entry: String,
+    expected_sha256: Option<String>,
+) -> Result<InstallStatus, String> {
+    let relative = Path::new(&entry);
+    if !stays_inside(relative) {
+        return Err(format!(
+            "Entry must be a path inside the install dir: {}",
+            entry
+        ));
+    }
+
+    let file = Path::new(&install_dir).join(relative);
+    if !file.is_file() {
+        return Ok(InstallStatus {
+            installed: false,
+            verified: false,
+        });
+    }
+
+    let verified = match expected_sha256.as_deref().map(str::trim) {
+        Some(expected) if !expected.is_empty() => sha256_matches(file, expected).await?,
+        _ => false,
+    };
+    Ok(InstallStatus {
+        installed: true,
+        verified,
+    })
+}
+
+// Hash a whole extracted game folder without adding it (`ipfs add -n`) and
+// compare the root CID to the published one
+#[tauri::command]
+pub async fn verify_installed_dir(
+    dir: String,
+    expected_cid: String,
+    app_handle: AppHandle,
+) -> Result<bool, String> {
+    let expected = expected_cid.trim();
+    if expected.is_empty() {
+        return Err("Expected CID must not be empty".to_string());
+    }
+
+    let root = PathBuf::from(&dir);
+    if !root.is_dir() {
+        return Err(format!("Install directory not found: {}", dir));
+    }
+    let is_empty = std::fs::read_dir(&root)
+        .map_err(|e| format!("Failed to read {}: {}", dir, e))?
+        .next()
+        .is_none();
+    if is_empty {
+        return Err(format!("Install directory is empty: {}", dir));
+    }
+
+    // The root CID depends on the CID version, so match the published one
+    let cid_version = if expected.starts_with("Qm") { "0" } else { "1" };
+    let actual = ipfs::run_ipfs(
+        &app_handle,
+        &[
+            "add",
+            "-n",
+            "-r",
+            "-Q",
+            &format!("--cid-version={}", cid_version),
+            &dir,
+        ],
+    )
+    .await?;
+
+    let matches = actual == expected;
+    println!(
+        "[Integrity] Verified {}: {}",
+        dir,
+        if matches { "ok" } else { "root CID mismatch" }
+    );
+    Ok(matches)
+}
@@ -0,0 +1,1185 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{async_runtime, AppHandle, Emitter, Manager};
+use tauri_plugin_http::reqwest;
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_shell::{process::Command, ShellExt};
+
+use crate::settings::{self, Settings};
+use crate::{api, http, ProcessManager};
+
+// How long to wait for the local daemon before treating it as offline
+const DAEMON_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+// How long a previous run's daemon gets to exit after a shutdown request
+const DAEMON_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+// Per-gateway limit when benchmarking; slower gateways count as failed
+const GATEWAY_BENCHMARK_TIMEOUT: Duration = Duration::from_secs(5);
+// Large pins legitimately run far past the shared client's request timeout
+const PIN_TIMEOUT: Duration = Duration::from_secs(6 * 60 * 60);
+
+// Canonical local gateway written into published executable URLs; only the
+// CID is used when resolving them, so this doesn't follow the port setting
+pub const IPFS_GATEWAY_URL: &str = "http://127.0.0.1:8080";
+// Hosted WebUI; its origin is whitelisted by cors_commands
+const WEBUI_URL: &str = "https://webui.ipfs.io";
+// Public gateway, used for content the web needs to reach (covers, metadata)
+pub const PUBLIC_GATEWAY_URL: &str = "https://ipfs.io";
+
+pub fn gateway_url(gateway: &str, cid: &str) -> String {
+    format!("{}/ipfs/{}", gateway, cid)
+}
+
+// CID from an `ipfs://` URI or a gateway URL (`.../ipfs/<cid>`)
+pub fn extract_cid(url: &str) -> Option<String> {
+    if let Some(cid) = url.strip_prefix("ipfs://") {
+        return Some(cid.trim_end_matches('/').to_string()).filter(|cid| !cid.is_empty());
+    }
+
+    let (_, rest) = url.split_once("/ipfs/")?;
+    let cid: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    Some(cid).filter(|cid| !cid.is_empty())
+}
+
+// IPFS API CORS headers the webview and WebUI integration rely on, as (key, JSON value)
+pub fn cors_commands(api_port: u16) -> Vec<(&'static str, String)> {
+    vec![
+        ("API.HTTPHeaders.Access-Control-Allow-Origin", format!("[\"http://localhost:3000\", \"http://localhost:1420\", \"tauri://localhost\", \"https://webui.ipfs.io\", \"http://127.0.0.1:{}\"]", api_port)),
+        ("API.HTTPHeaders.Access-Control-Allow-Methods", "[\"PUT\", \"POST\", \"GET\"]".to_string()),
+    ]
+}
+
+// Listen addresses for the daemon's API and gateway, as (key, multiaddr)
+pub fn address_commands(settings: &Settings) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "Addresses.API",
+            format!("/ip4/127.0.0.1/tcp/{}", settings.ipfs_api_port),
+        ),
+        (
+            "Addresses.Gateway",
+            format!("/ip4/127.0.0.1/tcp/{}", settings.ipfs_gateway_port),
+        ),
+    ]
+}
+
+// Lock file the daemon holds inside IPFS_PATH while running
+const REPO_LOCK_FILE: &str = "repo.lock";
+
+pub fn ipfs_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(app_data_dir.join(".ipfs"))
+}
+
+// Environment passed to every IPFS sidecar invocation
+pub fn ipfs_env(app_handle: &AppHandle) -> Result<HashMap<String, String>, String> {
+    let mut env = HashMap::new();
+    env.insert(
+        "IPFS_PATH".to_string(),
+        ipfs_path(app_handle)?.to_string_lossy().to_string(),
+    );
+    Ok(env)
+}
+
+// The bundled IPFS CLI, refused unless it passed its startup checksum
+pub fn sidecar(app_handle: &AppHandle) -> Result<Command, String> {
+    app_handle
+        .state::<ProcessManager>()
+        .ensure_ipfs_binary_ok()?;
+    app_handle
+        .shell()
+        .sidecar("ipfs")
+        .map_err(|e| format!("IPFS sidecar not found: {}", e))
+}
+
+// Re-apply the CORS config from the setup hook, returning the keys that had drifted
+#[tauri::command]
+pub async fn ensure_ipfs_cors(app_handle: AppHandle) -> Result<Vec<String>, String> {
+    let env = ipfs_env(&app_handle)?;
+    let mut changed = Vec::new();
+
+    for (key, value) in cors_commands(settings::current(&app_handle).ipfs_api_port) {
+        let current = sidecar(&app_handle)?
+            .args(["config", key])
+            .envs(env.clone())
+            .output()
+            .await
+            .map_err(|e| format!("Failed to read IPFS config {}: {}", key, e))?;
+
+        let current_value = serde_json::from_slice::<serde_json::Value>(&current.stdout).ok();
+        let expected_value = serde_json::from_str::<serde_json::Value>(&value).ok();
+
+        if current.status.success() && current_value == expected_value {
+            continue;
+        }
+
+        let output = sidecar(&app_handle)?
+            .args(["config", key, &value, "--json"])
+            .envs(env.clone())
+            .output()
+            .await
+            .map_err(|e| format!("Failed to set IPFS config {}: {}", key, e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to set IPFS config {}: {}",
+                key,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        println!("[Tauri] Repaired IPFS config {}", key);
+        changed.push(key.to_string());
+    }
+
+    Ok(changed)
+}
+
+// Run the IPFS sidecar with the app's IPFS_PATH and return its trimmed stdout
+pub async fn run_ipfs(app_handle: &AppHandle, args: &[&str]) -> Result<String, String> {
+    let output = sidecar(app_handle)?
+        .args(args)
+        .envs(ipfs_env(app_handle)?)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ipfs {}: {}", args.join(" "), e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "ipfs {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Add (and pin) a file, returning its CIDv1
+pub async fn add_file(app_handle: &AppHandle, path: &str) -> Result<String, String> {
+    run_ipfs(
+        app_handle,
+        &["add", "-Q", "--pin=true", "--cid-version=1", path],
+    )
+    .await
+}
+
+#[derive(Clone, Serialize)]
+struct PinProgress {
+    cid: String,
+    // Blocks fetched so far; the total isn't known until the pin completes
+    blocks: u64,
+}
+
+// Incremental parser for the pin/add progress stream. Chunks are fed as they
+// arrive and lines may be split across them.
+#[derive(Default)]
+struct PinStream {
+    buffer: Vec<u8>,
+    pinned: bool,
+}
+
+impl PinStream {
+    // Block counts reported by the lines `chunk` completes
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<u64>, String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut progress = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            progress.extend(self.handle_line(&line)?);
+        }
+        Ok(progress)
+    }
+
+    // Handle an unterminated last line; the stream must have ended with
+    // `{"Pins": [...]}` for the pin to count
+    fn finish(mut self) -> Result<Vec<u64>, String> {
+        let line = std::mem::take(&mut self.buffer);
+        let progress = self.handle_line(&line)?;
+        if self.pinned {
+            Ok(progress.into_iter().collect())
+        } else {
+            Err("Pin stream ended without confirmation".to_string())
+        }
+    }
+
+    fn handle_line(&mut self, line: &[u8]) -> Result<Option<u64>, String> {
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(line) else {
+            return Ok(None);
+        };
+        if let Some(message) = value.get("Message").and_then(|m| m.as_str()) {
+            return Err(format!("IPFS pin failed: {}", message));
+        }
+        if value.get("Pins").is_some() {
+            self.pinned = true;
+        }
+        Ok(value.get("Progress").and_then(|p| p.as_u64()))
+    }
+}
+
+// Pin through the HTTP API's streaming endpoint, emitting `pin-progress` for
+// each progress line
+async fn pin_with_progress(app_handle: &AppHandle, cid: &str) -> Result<(), String> {
+    let client = http::client(app_handle);
+    let url = format!(
+        "{}/api/v0/pin/add?arg={}&progress=true",
+        settings::current(app_handle).ipfs_api_url(),
+        cid
+    );
+
+    let mut response = client
+        .post(url)
+        .timeout(PIN_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach IPFS API: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("IPFS pin request failed: {}", response.status()));
+    }
+
+    let emit_progress = |blocks: u64| {
+        let _ = app_handle.emit(
+            "pin-progress",
+            PinProgress {
+                cid: cid.to_string(),
+                blocks,
+            },
+        );
+    };
+
+    let mut stream = PinStream::default();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Pin stream interrupted: {}", e))?
+    {
+        stream.feed(&chunk)?.into_iter().for_each(emit_progress);
+    }
+    stream.finish()?.into_iter().for_each(emit_progress);
+    Ok(())
+}
+
+// Recursively pin a CID, reporting progress when the HTTP API is available
+#[tauri::command]
+pub async fn pin_cid(cid: String, app_handle: AppHandle) -> Result<(), String> {
+    match pin_with_progress(&app_handle, &cid).await {
+        Ok(()) => {}
+        Err(e) => {
+            eprintln!("[IPFS] Progress pin failed, falling back to CLI: {}", e);
+            run_ipfs(&app_handle, &["pin", "add", &cid]).await?;
+        }
+    }
+
+    println!("[IPFS] Pinned {}", cid);
+    Ok(())
+}
+
+pub async fn unpin(app_handle: &AppHandle, cid: &str) -> Result<(), String> {
+    run_ipfs(app_handle, &["pin", "rm", cid]).await.map(|_| ())
+}
+
+// Whether the local daemon's HTTP API answers
+pub async fn daemon_reachable(client: &reqwest::Client, api_url: &str) -> bool {
+    client
+        .post(format!("{}/api/v0/version", api_url))
+        .timeout(DAEMON_PROBE_TIMEOUT)
+        .send()
+        .await
+        .map(|response| response.status().is_success())
+        .unwrap_or(false)
+}
+
+// Ask a daemon we don't own to exit, waiting until its API stops answering
+pub async fn shutdown_daemon(client: &reqwest::Client, api_url: &str) -> Result<(), String> {
+    client
+        .post(format!("{}/api/v0/shutdown", api_url))
+        .timeout(DAEMON_PROBE_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to shut down IPFS daemon: {}", e))?;
+
+    let deadline = Instant::now() + DAEMON_SHUTDOWN_TIMEOUT;
+    while daemon_reachable(client, api_url).await {
+        if Instant::now() >= deadline {
+            return Err("IPFS daemon is still running after shutdown".to_string());
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    Ok(())
+}
+
+// Gateway URL for an executable: the local gateway when the daemon is up,
+// otherwise the first server-recommended public gateway, then the configured ones
+#[tauri::command]
+pub async fn resolve_executable_url(url: String, app_handle: AppHandle) -> Result<String, String> {
+    let cid = extract_cid(&url).ok_or_else(|| format!("Invalid executable URL: {}", url))?;
+    let client = http::client(&app_handle);
+    let settings = settings::current(&app_handle);
+
+    if daemon_reachable(&client, &settings.ipfs_api_url()).await {
+        return Ok(gateway_url(&settings.ipfs_gateway_url(), &cid));
+    }
+
+    let configured = settings
+        .gateways
+        .first()
+        .cloned()
+        .unwrap_or_else(|| PUBLIC_GATEWAY_URL.to_string());
+
+    let gateway = match api::fetch_gateways(&client, &api::base_url(&app_handle)).await {
+        Ok(gateways) if !gateways.is_empty() => gateways[0].clone(),
+        Ok(_) => configured,
+        Err(e) => {
+            eprintln!(
+                "[IPFS] Failed to fetch gateway list, using configured: {}",
+                e
+            );
+            configured
+        }
+    };
+
+    println!("[IPFS] Local daemon offline, resolving via {}", gateway);
+    Ok(gateway_url(&gateway, &cid))
+}
+
+// Unpin recursive pins that no installed game references, optionally running GC
+#[tauri::command]
+pub async fn prune_orphan_pins(
+    known_cids: Vec<String>,
+    run_gc: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, String> {
+    let known: HashSet<String> = known_cids.into_iter().collect();
+
+    let pins = run_ipfs(&app_handle, &["pin", "ls", "--type=recursive", "-q"]).await?;
+    let orphans: Vec<String> = pins
+        .lines()
+        .map(str::trim)
+        .filter(|cid| !cid.is_empty() && !known.contains(*cid))
+        .map(str::to_string)
+        .collect();
+
+    let mut removed = Vec::new();
+    for cid in orphans {
+        match unpin(&app_handle, &cid).await {
+            Ok(()) => removed.push(cid),
+            Err(e) => eprintln!("[IPFS] Failed to unpin orphan {}: {}", cid, e),
+        }
+    }
+
+    println!("[IPFS] Pruned {} orphaned pin(s)", removed.len());
+
+    if run_gc.unwrap_or(false) && !removed.is_empty() {
+        run_ipfs(&app_handle, &["repo", "gc", "-q"]).await?;
+        println!("[IPFS] Garbage collection completed");
+    }
+
+    Ok(removed)
+}
+
+#[derive(Serialize)]
+pub struct BootstrapUpdate {
+    pub peers: Vec<String>,
+    // Bootstrap changes only take effect after the daemon restarts
+    pub restart_required: bool,
+}
+
+// Bootstrap multiaddrs must name a transport address and end in a peer id,
+// e.g. /dnsaddr/bootstrap.libp2p.io/p2p/Qm... or /ip4/1.2.3.4/tcp/4001/p2p/12D3...
+fn is_valid_bootstrap_multiaddr(addr: &str) -> bool {
+    let Some(rest) = addr.strip_prefix('/') else {
+        return false;
+    };
+    let parts: Vec<&str> = rest.split('/').collect();
+
+    let known_base = matches!(
+        parts.first(),
+        Some(&("ip4" | "ip6" | "dns" | "dns4" | "dns6" | "dnsaddr"))
+    );
+    let has_peer_id = parts.len() >= 4
+        && matches!(parts[parts.len() - 2], "p2p" | "ipfs")
+        && parts[parts.len() - 1]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric())
+        && !parts[parts.len() - 1].is_empty();
+
+    known_base && has_peer_id && parts.iter().all(|part| !part.is_empty())
+}
+
+#[tauri::command]
+pub async fn get_bootstrap_peers(app_handle: AppHandle) -> Result<Vec<String>, String> {
+    let output = run_ipfs(&app_handle, &["bootstrap", "list"]).await?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+// Replace the bootstrap list; takes effect on the next daemon start
+#[tauri::command]
+pub async fn set_bootstrap_peers(
+    peers: Vec<String>,
+    app_handle: AppHandle,
+) -> Result<BootstrapUpdate, String> {
+    if peers.is_empty() {
+        return Err("At least one bootstrap peer is required".to_string());
+    }
+
+    let invalid: Vec<&str> = peers
+        .iter()
+        .map(|peer| peer.trim())
+        .filter(|peer| !is_valid_bootstrap_multiaddr(peer))
+        .collect();
+    if !invalid.is_empty() {
+        return Err(format!(
+            "Invalid bootstrap multiaddr(s): {}",
+            invalid.join(", ")
+        ));
+    }
+
+    run_ipfs(&app_handle, &["bootstrap", "rm", "--all"]).await?;
+
+    let mut args = vec!["bootstrap", "add"];
+    args.extend(peers.iter().map(|peer| peer.trim()));
+    run_ipfs(&app_handle, &args).await?;
+
+    println!(
+        "[IPFS] Bootstrap peers replaced ({}), restart required",
+        peers.len()
+    );
+
+    Ok(BootstrapUpdate {
+        peers: get_bootstrap_peers(app_handle).await?,
+        restart_required: true,
+    })
+}
+
+// Totals are in bytes, rates in bytes per second
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Bandwidth {
+    pub total_in: u64,
+    pub total_out: u64,
+    pub rate_in: f64,
+    pub rate_out: f64,
+}
+
+// "1.5 MB", "512 B/s", "2.0 KiB/s" or a bare number, as bytes
+fn parse_byte_value(value: &str) -> Option<f64> {
+    let value = value.trim().trim_end_matches("/s").trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == 'e' || c == '-' || c == '+'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.trim().parse().ok()?;
+
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "kb" => 1e3,
+        "mb" => 1e6,
+        "gb" => 1e9,
+        "tb" => 1e12,
+        "kib" => 1024.0,
+        "mib" => 1024.0_f64.powi(2),
+        "gib" => 1024.0_f64.powi(3),
+        "tib" => 1024.0_f64.powi(4),
+        _ => return None,
+    };
+    Some(number * multiplier)
+}
+
+// `stats bw` output: JSON when the version supports --enc=json, otherwise
+// the human-readable "TotalIn: 1.2 MB" lines older versions print
+fn parse_bandwidth(output: &str) -> Option<Bandwidth> {
+    let mut fields: HashMap<String, f64> = HashMap::new();
+
+    if let Ok(serde_json::Value::Object(map)) = serde_json::from_str(output) {
+        for (key, value) in map {
+            let number = match &value {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => parse_byte_value(s),
+                _ => None,
+            };
+            if let Some(number) = number {
+                fields.insert(key.to_ascii_lowercase(), number);
+            }
+        }
+    } else {
+        for line in output.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                if let Some(number) = parse_byte_value(value) {
+                    fields.insert(key.trim().to_ascii_lowercase(), number);
+                }
+            }
+        }
+    }
+
+    let field = |key: &str| fields.get(key).copied();
+    Some(Bandwidth {
+        total_in: field("totalin")? as u64,
+        total_out: field("totalout")? as u64,
+        rate_in: field("ratein").unwrap_or_default(),
+        rate_out: field("rateout").unwrap_or_default(),
+    })
+}
+
+// Current bandwidth totals and rates; poll it to chart network activity
+#[tauri::command]
+pub async fn ipfs_bandwidth(app_handle: AppHandle) -> Result<Bandwidth, String> {
+    if !app_handle.state::<ProcessManager>().ipfs_running() {
+        return Err("IPFS daemon is not running".to_string());
+    }
+
+    let output = match run_ipfs(&app_handle, &["stats", "bw", "--enc=json"]).await {
+        Ok(output) => output,
+        Err(_) => run_ipfs(&app_handle, &["stats", "bw"]).await?,
+    };
+
+    parse_bandwidth(&output).ok_or_else(|| format!("Unrecognized bandwidth output: {}", output))
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VersionInfo {
+    pub version: String,
+    pub commit: Option<String>,
+    pub repo_version: Option<String>,
+    pub system: Option<String>,
+    pub golang_version: Option<String>,
+}
+
+// Parse `ipfs version --all`, one `Key: value` per line:
+//
+//   Kubo version: 0.29.0-3f0947b
+//   Repo version: 15
+//   System version: amd64/linux
+//   Golang version: go1.22.4
+//
+// Older builds say `go-ipfs version` and report the commit on its own line.
+fn parse_version_info(output: &str) -> Option<VersionInfo> {
+    let mut info = VersionInfo::default();
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        match key.trim().to_ascii_lowercase().as_str() {
+            "kubo version" | "go-ipfs version" | "ipfs version" => {
+                // A trailing commit hash (`0.30.0-rc1-3f0947b`), not a pre-release tag
+                let commit = value.rsplit_once('-').filter(|(_, commit)| {
+                    commit.len() >= 7 && commit.chars().all(|c| c.is_ascii_hexdigit())
+                });
+                match commit {
+                    Some((version, commit)) => {
+                        info.version = version.to_string();
+                        info.commit = Some(commit.to_string());
+                    }
+                    None => info.version = value.to_string(),
+                }
+            }
+            "commit" => info.commit = Some(value.to_string()),
+            "repo version" => info.repo_version = Some(value.to_string()),
+            "system version" => info.system = Some(value.to_string()),
+            "golang version" => info.golang_version = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    (!info.version.is_empty()).then_some(info)
+}
+
+// Details of the bundled sidecar build, for bug reports
+#[tauri::command]
+pub async fn ipfs_version_info(app_handle: AppHandle) -> Result<VersionInfo, String> {
+    let output = run_ipfs(&app_handle, &["version", "--all"]).await?;
+    parse_version_info(&output).ok_or_else(|| format!("Unrecognized version output: {}", output))
+}
+
+// Swarm address filters covering every IPv4 and IPv6 peer
+const BLOCK_ALL_FILTERS: &[&str] = &["/ip4/0.0.0.0/ipcidr/0", "/ip6/::/ipcidr/0"];
+
+// Pause or resume peer-to-peer traffic without restarting the daemon.
+// Pausing drops every connection and installs swarm filters so peers can't
+// reconnect; the filters are saved to the config, so a paused node stays
+// paused across restarts until resumed. Resuming removes them and redials the
+// bootstrap peers. The local API and gateway keep working throughout.
+#[tauri::command]
+pub async fn ipfs_set_network(enabled: bool, app_handle: AppHandle) -> Result<bool, String> {
+    if enabled {
+        for filter in BLOCK_ALL_FILTERS {
+            // Not present when the network wasn't paused
+            if let Err(e) = run_ipfs(&app_handle, &["swarm", "filters", "rm", filter]).await {
+                println!("[IPFS] Swarm filter {} not removed: {}", filter, e);
+            }
+        }
+
+        let peers = get_bootstrap_peers(app_handle.clone()).await?;
+        let mut connected = 0;
+        for peer in &peers {
+            match run_ipfs(&app_handle, &["swarm", "connect", peer]).await {
+                Ok(_) => connected += 1,
+                Err(e) => eprintln!("[IPFS] Failed to reconnect {}: {}", peer, e),
+            }
+        }
+        println!(
+            "[IPFS] Network resumed, reconnected {} of {} bootstrap peers",
+            connected,
+            peers.len()
+        );
+    } else {
+        for filter in BLOCK_ALL_FILTERS {
+            run_ipfs(&app_handle, &["swarm", "filters", "add", filter]).await?;
+        }
+
+        let peers = run_ipfs(&app_handle, &["swarm", "peers"]).await?;
+        let peers: Vec<&str> = peers
+            .lines()
+            .map(str::trim)
+            .filter(|peer| !peer.is_empty())
+            .collect();
+        for peer in &peers {
+            // Peers may drop on their own while we iterate
+            if let Err(e) = run_ipfs(&app_handle, &["swarm", "disconnect", peer]).await {
+                println!("[IPFS] Failed to disconnect {}: {}", peer, e);
+            }
+        }
+        println!("[IPFS] Network paused, disconnected {} peers", peers.len());
+    }
+
+    Ok(enabled)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reachability {
+    // Advertises a directly dialable public address
+    Public,
+    // Only private or relayed addresses; inbound connections need a relay
+    BehindNat,
+    // No peers connected, so nothing can be inferred yet
+    Unknown,
+}
+
+#[derive(Serialize)]
+pub struct NatStatus {
+    pub reachability: Reachability,
+    pub public_addresses: Vec<String>,
+    pub peer_count: usize,
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // 100.64.0.0/10 is carrier-grade NAT
+    let shared = a == 100 && (64..128).contains(&b);
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || shared)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    // fc00::/7 unique local, fe80::/10 link local
+    let unique_local = first & 0xfe00 == 0xfc00;
+    let link_local = first & 0xffc0 == 0xfe80;
+    !(ip.is_loopback() || ip.is_unspecified() || unique_local || link_local)
+}
+
+// Whether a multiaddr such as `/ip4/1.2.3.4/tcp/4001/p2p/<id>` is directly
+// dialable from the internet. Relayed (`/p2p-circuit`) and DNS addresses
+// don't prove reachability, and malformed ones are skipped.
+fn is_public_multiaddr(addr: &str) -> bool {
+    let mut parts = addr.split('/').filter(|part| !part.is_empty());
+    let public = match (parts.next(), parts.next()) {
+        (Some("ip4"), Some(ip)) => ip.parse().map(is_public_ipv4).unwrap_or(false),
+        (Some("ip6"), Some(ip)) => ip.parse().map(is_public_ipv6).unwrap_or(false),
+        _ => false,
+    };
+    public && !parts.any(|part| part == "p2p-circuit")
+}
+
+#[derive(Deserialize)]
+struct IdOutput {
+    #[serde(rename = "Addresses", default)]
+    addresses: Option<Vec<String>>,
+}
+
+// Infer whether this node is reachable from its advertised addresses. Kubo
+// only lists observed public addresses once peers have confirmed them.
+#[tauri::command]
+pub async fn ipfs_nat_status(app_handle: AppHandle) -> Result<NatStatus, String> {
+    let peers = run_ipfs(&app_handle, &["swarm", "peers"]).await?;
+    let peer_count = peers.lines().filter(|line| !line.trim().is_empty()).count();
+
+    let id = run_ipfs(&app_handle, &["id", "--enc=json"]).await?;
+    let id: IdOutput =
+        serde_json::from_str(&id).map_err(|e| format!("Invalid ipfs id output: {}", e))?;
+
+    let mut public_addresses: Vec<String> = id
+        .addresses
+        .unwrap_or_default()
+        .into_iter()
+        .map(|addr| addr.trim().to_string())
+        .filter(|addr| is_public_multiaddr(addr))
+        .collect();
+    public_addresses.sort();
+    public_addresses.dedup();
+
+    let reachability = if !public_addresses.is_empty() {
+        Reachability::Public
+    } else if peer_count > 0 {
+        Reachability::BehindNat
+    } else {
+        Reachability::Unknown
+    };
+
+    println!(
+        "[IPFS] NAT status {:?} ({} peers, {} public addresses)",
+        reachability,
+        peer_count,
+        public_addresses.len()
+    );
+    Ok(NatStatus {
+        reachability,
+        public_addresses,
+        peer_count,
+    })
+}
+
+// Bandwidth readings averaged for a download estimate, and the gap between them
+const ESTIMATE_SAMPLES: u32 = 3;
+const ESTIMATE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+// Below this the daemon is effectively idle and an ETA would be meaningless
+const MIN_ESTIMATE_RATE: f64 = 1024.0;
+
+#[derive(Serialize)]
+pub struct EstimateResult {
+    // None when the current rate is too low to extrapolate from
+    pub seconds: Option<u64>,
+    // Averaged incoming rate in bytes per second
+    pub rate_in: f64,
+}
+
+// Time-remaining estimate for downloading `size_bytes` at the current rate
+#[tauri::command]
+pub async fn estimate_download(
+    size_bytes: u64,
+    app_handle: AppHandle,
+) -> Result<EstimateResult, String> {
+    let mut total_rate = 0.0;
+    for sample in 0..ESTIMATE_SAMPLES {
+        if sample > 0 {
+            tokio::time::sleep(ESTIMATE_SAMPLE_INTERVAL).await;
+        }
+        total_rate += ipfs_bandwidth(app_handle.clone()).await?.rate_in;
+    }
+    let rate_in = total_rate / ESTIMATE_SAMPLES as f64;
+
+    let seconds =
+        (rate_in >= MIN_ESTIMATE_RATE).then(|| (size_bytes as f64 / rate_in).ceil() as u64);
+    Ok(EstimateResult { seconds, rate_in })
+}
+
+#[derive(Serialize)]
+pub struct SelfTestResult {
+    pub cid: String,
+    pub add_ms: u64,
+    pub cat_ms: u64,
+}
+
+// Round-trip check for "Run diagnostics": add a small unique file, read it
+// back by CID and compare, then unpin it and garbage collect
+#[tauri::command]
+pub async fn ipfs_selftest(app_handle: AppHandle) -> Result<SelfTestResult, String> {
+    let client = http::client(&app_handle);
+    if !daemon_reachable(&client, &settings::current(&app_handle).ipfs_api_url()).await {
+        return Err("Self-test failed: IPFS daemon is not reachable".to_string());
+    }
+
+    // Unique content so the add can't be satisfied by an earlier run's blocks
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let content = format!("gamex ipfs self-test {}", nonce);
+    let path = std::env::temp_dir().join(format!("gamex-selftest-{}.txt", nonce));
+    std::fs::write(&path, &content)
+        .map_err(|e| format!("Self-test failed to write test file: {}", e))?;
+
+    let started = Instant::now();
+    let added = add_file(&app_handle, &path.to_string_lossy()).await;
+    let add_ms = started.elapsed().as_millis() as u64;
+    let _ = std::fs::remove_file(&path);
+    let cid = added.map_err(|e| format!("Self-test add failed: {}", e))?;
+
+    let started = Instant::now();
+    let read = run_ipfs(&app_handle, &["cat", &cid]).await;
+    let cat_ms = started.elapsed().as_millis() as u64;
+
+    // Clean up before reporting a read failure so failed runs don't leak pins
+    let cleanup = match unpin(&app_handle, &cid).await {
+        Ok(()) => run_ipfs(&app_handle, &["repo", "gc", "-q"])
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Self-test garbage collection failed: {}", e)),
+        Err(e) => Err(format!("Self-test unpin of {} failed: {}", cid, e)),
+    };
+
+    let read = read.map_err(|e| format!("Self-test cat of {} failed: {}", cid, e))?;
+    if read != content {
+        return Err(format!(
+            "Self-test content mismatch: {} returned {} bytes, expected {}",
+            cid,
+            read.len(),
+            content.len()
+        ));
+    }
+    cleanup?;
+
+    println!(
+        "[IPFS] Self-test passed: {} (add {} ms, cat {} ms)",
+        cid, add_ms, cat_ms
+    );
+    Ok(SelfTestResult {
+        cid,
+        add_ms,
+        cat_ms,
+    })
+}
+
+// Routing.Type values the launcher lets users pick; dhtclient and none avoid
+// serving DHT queries, which is much lighter on laptops
+const ROUTING_MODES: &[&str] = &["dht", "dhtclient", "auto", "none"];
+// Kubo's behavior when Routing.Type is unset
+const DEFAULT_ROUTING_MODE: &str = "auto";
+
+#[derive(Serialize)]
+pub struct RoutingUpdate {
+    pub mode: String,
+    // Routing changes only take effect after the daemon restarts
+    pub restart_required: bool,
+}
+
+#[tauri::command]
+pub async fn get_ipfs_routing(app_handle: AppHandle) -> Result<String, String> {
+    // Unset keys make `config` fail or print null depending on the version
+    match run_ipfs(&app_handle, &["config", "Routing.Type"]).await {
+        Ok(mode) if !mode.is_empty() && mode != "null" => Ok(mode),
+        _ => Ok(DEFAULT_ROUTING_MODE.to_string()),
+    }
+}
+
+// Set Routing.Type; takes effect on the next daemon start
+#[tauri::command]
+pub async fn set_ipfs_routing(
+    mode: String,
+    app_handle: AppHandle,
+) -> Result<RoutingUpdate, String> {
+    let mode = mode.trim().to_lowercase();
+    if !ROUTING_MODES.contains(&mode.as_str()) {
+        return Err(format!(
+            "Invalid routing mode {}, expected one of: {}",
+            mode,
+            ROUTING_MODES.join(", ")
+        ));
+    }
+
+    run_ipfs(&app_handle, &["config", "Routing.Type", &mode]).await?;
+    println!("[IPFS] Routing type set to {}, restart required", mode);
+
+    Ok(RoutingUpdate {
+        mode,
+        restart_required: true,
+    })
+}
+
+// Open the hosted IPFS WebUI against the local daemon's API for debugging
+#[tauri::command]
+pub fn open_ipfs_webui(app_handle: AppHandle) -> Result<(), String> {
+    if !app_handle.state::<ProcessManager>().ipfs_running() {
+        return Err("IPFS daemon is not running, start IPFS to open the WebUI".to_string());
+    }
+
+    let api_port = settings::current(&app_handle).ipfs_api_port;
+    let url = format!("{}/#/?api=/ip4/127.0.0.1/tcp/{}", WEBUI_URL, api_port);
+
+    app_handle
+        .opener()
+        .open_url(&url, None::<&str>)
+        .map_err(|e| format!("Failed to open IPFS WebUI: {}", e))?;
+
+    println!("[IPFS] Opened WebUI for API port {}", api_port);
+    Ok(())
+}
+
+// Daemon startup failure caused by another process (or a stale file) holding repo.lock
+pub fn is_repo_lock_error(line: &str) -> bool {
+    line.contains(REPO_LOCK_FILE) && line.contains("someone else has the lock")
+}
+
+// Any running process that looks like an IPFS daemon (the sidecar, or a
+// system-wide kubo install sharing the port)
+fn ipfs_process_running() -> bool {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    system.processes().values().any(|process| {
+        let name = process.name().to_string_lossy().to_lowercase();
+        name == "ipfs" || name.starts_with("ipfs-") || name.starts_with("ipfs.")
+    })
+}
+
+// Remove repo.lock only when certain no daemon holds it: none tracked by the
+// launcher, none answering on the API port, and no ipfs process at all.
+// Returns whether a lock file was removed.
+pub async fn clear_stale_lock(app_handle: &AppHandle) -> Result<bool, String> {
+    if app_handle.state::<ProcessManager>().ipfs_running() {
+        return Err("The launcher's IPFS daemon is running".to_string());
+    }
+    let client = http::client(app_handle);
+    if daemon_reachable(&client, &settings::current(app_handle).ipfs_api_url()).await {
+        return Err("An IPFS daemon is answering on the API port".to_string());
+    }
+    if ipfs_process_running() {
+        return Err("An ipfs process is still running".to_string());
+    }
+
+    let lock = ipfs_path(app_handle)?.join(REPO_LOCK_FILE);
+    if !lock.exists() {
+        return Ok(false);
+    }
+
+    std::fs::remove_file(&lock).map_err(|e| format!("Failed to remove {:?}: {}", lock, e))?;
+    println!("[IPFS] Removed stale repo lock {:?}", lock);
+    Ok(true)
+}
+
+// Manual recovery for "someone else has the lock" startup failures
+#[tauri::command]
+pub async fn clear_ipfs_lock(app_handle: AppHandle) -> Result<bool, String> {
+    clear_stale_lock(&app_handle).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct GatewayResult {
+    pub url: String,
+    pub ms: u64,
+    pub ok: bool,
+}
+
+// Fetch a small known CID from every gateway at once and time each; results
+// are sorted with working gateways first, fastest first
+#[tauri::command]
+pub async fn benchmark_gateways(
+    gateways: Vec<String>,
+    test_cid: String,
+    app_handle: AppHandle,
+) -> Result<Vec<GatewayResult>, String> {
+    if gateways.is_empty() {
+        return Err("At least one gateway is required".to_string());
+    }
+
+    let client = http::client(&app_handle);
+    let tasks: Vec<_> = gateways
+        .into_iter()
+        .map(|gateway| {
+            let gateway = gateway.trim().trim_end_matches('/').to_string();
+            let url = gateway_url(&gateway, &test_cid);
+            let client = client.clone();
+            async_runtime::spawn(async move {
+                let started = Instant::now();
+                let response = client
+                    .get(url)
+                    .timeout(GATEWAY_BENCHMARK_TIMEOUT)
+                    .send()
+                    .await;
+                // Time the full body so slow transfers don't look fast
+                let ok = match response {
+                    Ok(response) if response.status().is_success() => {
+                        response.bytes().await.is_ok()
+                    }
+                    _ => false,
+                };
+                GatewayResult {
+                    url: gateway,
+                    ms: started.elapsed().as_millis() as u64,
+                    ok,
+                }
+            })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(
+            task.await
+                .map_err(|e| format!("Benchmark task failed: {}", e))?,
+        );
+    }
+    results.sort_by_key(|result| (!result.ok, result.ms));
+
+    println!(
+        "[IPFS] Benchmarked {} gateway(s), fastest: {:?}",
+        results.len(),
+        results
+            .first()
+            .filter(|result| result.ok)
+            .map(|result| &result.url)
+    );
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // As streamed by `pin/add?progress=true`, one JSON object per line
+    const PIN_STREAM: &[u8] = b"{\"Progress\":1}\n{\"Progress\":12}\n{\"Progress\":40}\n\
+        {\"Pins\":[\"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi\"]}\n";
+
+    #[test]
+    fn reports_progress_then_confirms_the_pin() {
+        let mut stream = PinStream::default();
+        assert_eq!(stream.feed(PIN_STREAM), Ok(vec![1, 12, 40]));
+        assert_eq!(stream.finish(), Ok(vec![]));
+    }
+
+    #[test]
+    fn lines_split_across_chunks_are_reassembled() {
+        let mut stream = PinStream::default();
+        let mut progress = Vec::new();
+        for chunk in PIN_STREAM.chunks(5) {
+            progress.extend(stream.feed(chunk).unwrap());
+        }
+        assert_eq!(progress, [1, 12, 40]);
+        assert_eq!(stream.finish(), Ok(vec![]));
+    }
+
+    #[test]
+    fn final_line_without_a_newline_still_counts() {
+        let mut stream = PinStream::default();
+        assert_eq!(
+            stream.feed(b"{\"Progress\":3}\n{\"Pins\":[\"cid\"]}"),
+            Ok(vec![3])
+        );
+        assert_eq!(stream.finish(), Ok(vec![]));
+    }
+
+    #[test]
+    fn errors_and_unconfirmed_streams_fail() {
+        let mut stream = PinStream::default();
+        assert_eq!(
+            stream.feed(b"{\"Progress\":2}\n{\"Message\":\"context canceled\",\"Code\":0}\n"),
+            Err("IPFS pin failed: context canceled".to_string())
+        );
+
+        // Cut off before the Pins line, e.g. the daemon went away
+        let mut stream = PinStream::default();
+        assert_eq!(stream.feed(b"{\"Progress\":2}\n{\"Progr"), Ok(vec![2]));
+        assert!(stream.finish().is_err());
+    }
+
+    #[test]
+    fn unparseable_lines_are_skipped() {
+        let mut stream = PinStream::default();
+        assert_eq!(
+            stream.feed(b"\n  \nnot json\n{\"Progress\":5}\n{\"Pins\":[]}\n"),
+            Ok(vec![5])
+        );
+        assert_eq!(stream.finish(), Ok(vec![]));
+    }
+
+    #[test]
+    fn byte_values_accept_si_and_binary_units() {
+        assert_eq!(parse_byte_value("512 B/s"), Some(512.0));
+        assert_eq!(parse_byte_value("1.5 MB"), Some(1_500_000.0));
+        assert_eq!(parse_byte_value("2.0 KiB/s"), Some(2048.0));
+        assert_eq!(parse_byte_value("1 GiB"), Some(1024.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_byte_value(" 42 "), Some(42.0));
+        assert_eq!(parse_byte_value("3 parsecs"), None);
+        assert_eq!(parse_byte_value("MB"), None);
+    }
+
+    #[test]
+    fn bandwidth_parses_json_output() {
+        let bandwidth =
+            parse_bandwidth(r#"{"TotalIn":1200000,"TotalOut":3400,"RateIn":512.5,"RateOut":0}"#)
+                .unwrap();
+        assert_eq!((bandwidth.total_in, bandwidth.total_out), (1_200_000, 3400));
+        assert_eq!((bandwidth.rate_in, bandwidth.rate_out), (512.5, 0.0));
+    }
+
+    #[test]
+    fn bandwidth_parses_text_output() {
+        let output =
+            "Bandwidth\nTotalIn: 1.2 MB\nTotalOut: 3.4 kB\nRateIn: 2.0 KiB/s\nRateOut: 10 B/s\n";
+        let bandwidth = parse_bandwidth(output).unwrap();
+        assert_eq!((bandwidth.total_in, bandwidth.total_out), (1_200_000, 3400));
+        assert_eq!((bandwidth.rate_in, bandwidth.rate_out), (2048.0, 10.0));
+    }
+
+    #[test]
+    fn bandwidth_needs_both_totals() {
+        // Rates default to zero, totals don't
+        let bandwidth = parse_bandwidth("TotalIn: 10 B\nTotalOut: 20 B").unwrap();
+        assert_eq!(bandwidth.rate_in, 0.0);
+        assert!(parse_bandwidth(r#"{"TotalIn":10}"#).is_none());
+        assert!(parse_bandwidth("Error: daemon not running").is_none());
+    }
+
+    #[test]
+    fn version_info_parses_kubo_output() {
+        let info = parse_version_info(
+            "Kubo version: 0.29.0-3f0947b\nRepo version: 15\nSystem version: amd64/linux\nGolang version: go1.22.4\n",
+        )
+        .unwrap();
+        assert_eq!(info.version, "0.29.0");
+        assert_eq!(info.commit.as_deref(), Some("3f0947b"));
+        assert_eq!(info.repo_version.as_deref(), Some("15"));
+        assert_eq!(info.system.as_deref(), Some("amd64/linux"));
+        assert_eq!(info.golang_version.as_deref(), Some("go1.22.4"));
+    }
+
+    #[test]
+    fn pre_release_tags_are_not_commits() {
+        let info = parse_version_info("Kubo version: 0.30.0-rc1").unwrap();
+        assert_eq!(info.version, "0.30.0-rc1");
+        assert!(info.commit.is_none());
+
+        let info = parse_version_info("Kubo version: 0.30.0-rc1-3f0947b").unwrap();
+        assert_eq!(info.version, "0.30.0-rc1");
+        assert_eq!(info.commit.as_deref(), Some("3f0947b"));
+    }
+
+    #[test]
+    fn older_builds_report_the_commit_separately() {
+        let info =
+            parse_version_info("go-ipfs version: 0.12.2\nCommit: 0e8b121\nRepo version: 12\n")
+                .unwrap();
+        assert_eq!(info.version, "0.12.2");
+        assert_eq!(info.commit.as_deref(), Some("0e8b121"));
+        assert!(info.system.is_none());
+    }
+
+    #[test]
+    fn output_without_a_version_is_rejected() {
+        assert!(parse_version_info("Repo version: 15\nSystem version: amd64/linux").is_none());
+        assert!(parse_version_info("").is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+// Daemon output lines kept for the in-app console
+const MAX_LOG_LINES: usize = 2000;
+
+// Ring buffer of the IPFS daemon's stdout/stderr
+#[derive(Default)]
+pub struct IpfsLogs {
+    lines: Mutex<VecDeque<String>>,
+}
+
+impl IpfsLogs {
+    pub fn push(&self, line: &str) {
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        for line in line.lines().filter(|line| !line.trim().is_empty()) {
+            if lines.len() == MAX_LOG_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_string());
+        }
+    }
+
+    fn filtered(&self, min_level: LogLevel, limit: usize) -> Vec<String> {
+        let Ok(lines) = self.lines.lock() else {
+            return Vec::new();
+        };
+        let mut matching: Vec<String> = lines
+            .iter()
+            .rev()
+            .filter(|line| line_level(line) >= min_level)
+            .take(limit)
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn parse(level: &str) -> Option<Self> {
+        match level.to_ascii_lowercase().as_str() {
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" | "dpanic" | "panic" | "fatal" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+// go-log lines look like `<timestamp>\tERROR\t<subsystem>\t<file:line>\t<msg>`.
+// Plain daemon output ("Daemon is ready") has no level and counts as info.
+fn line_level(line: &str) -> LogLevel {
+    line.split_whitespace()
+        .take(2)
+        .find_map(LogLevel::parse)
+        .unwrap_or(LogLevel::Info)
+}
+
+// Last `limit` captured daemon lines at or above `level` (error/warn/info),
+// oldest first. An unknown level returns every line.
+#[tauri::command]
+pub fn ipfs_logs_filtered(level: String, limit: usize, app_handle: AppHandle) -> Vec<String> {
+    let min_level = LogLevel::parse(level.trim()).unwrap_or_else(|| {
+        eprintln!("[IPFS] Unknown log level {:?}, returning all lines", level);
+        LogLevel::Debug
+    });
+
+    app_handle.state::<IpfsLogs>().filtered(min_level, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERROR_LINE: &str =
+        "2024-03-01T12:00:00.000Z\tERROR\tcore\tnode/builder.go:42\tfailed to dial peer";
+    const WARN_LINE: &str = "2024-03-01T12:00:01.000Z\tWARN\tswarm\tswarm/dial.go:7\tdial backoff";
+    const DEBUG_LINE: &str =
+        "2024-03-01T12:00:02.000Z\tDEBUG\tbitswap\tbitswap.go:1\twant list updated";
+
+    #[test]
+    fn levels_parse_case_insensitively() {
+        assert_eq!(LogLevel::parse("WARNING"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::parse("Info"), Some(LogLevel::Info));
+        assert_eq!(LogLevel::parse("fatal"), Some(LogLevel::Error));
+        assert_eq!(LogLevel::parse("dpanic"), Some(LogLevel::Error));
+        assert_eq!(LogLevel::parse("verbose"), None);
+        assert!(LogLevel::Debug < LogLevel::Info && LogLevel::Warn < LogLevel::Error);
+    }
+
+    #[test]
+    fn line_levels_come_from_the_go_log_column() {
+        assert_eq!(line_level(ERROR_LINE), LogLevel::Error);
+        assert_eq!(line_level(WARN_LINE), LogLevel::Warn);
+        assert_eq!(line_level(DEBUG_LINE), LogLevel::Debug);
+        assert_eq!(line_level("Daemon is ready"), LogLevel::Info);
+        // Only the leading columns count, not words in the message
+        assert_eq!(
+            line_level("Swarm listening, no error so far"),
+            LogLevel::Info
+        );
+    }
+
+    #[test]
+    fn filtering_keeps_the_newest_matching_lines_in_order() {
+        let logs = IpfsLogs::default();
+        logs.push(&format!("{}\nDaemon is ready\n\n{}", DEBUG_LINE, WARN_LINE));
+        logs.push(ERROR_LINE);
+
+        assert_eq!(logs.filtered(LogLevel::Warn, 10), [WARN_LINE, ERROR_LINE]);
+        assert_eq!(logs.filtered(LogLevel::Info, 2), [WARN_LINE, ERROR_LINE]);
+        assert_eq!(logs.filtered(LogLevel::Debug, 10).len(), 4);
+        assert!(logs.filtered(LogLevel::Error, 0).is_empty());
+    }
+
+    #[test]
+    fn old_lines_fall_out_of_the_buffer() {
+        let logs = IpfsLogs::default();
+        for index in 0..MAX_LOG_LINES + 3 {
+            logs.push(&format!("line {}", index));
+        }
+        let lines = logs.filtered(LogLevel::Debug, usize::MAX);
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert_eq!(lines[0], "line 3");
+    }
+}
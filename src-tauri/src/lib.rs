@@ -1,33 +1,396 @@
-use std::collections::HashMap;
+mod api;
+mod auth;
+mod catalog;
+mod deep_link;
+mod download;
+mod history;
+mod http;
+mod integrity;
+mod ipfs;
+mod ipfs_logs;
+mod library;
+mod platform;
+mod priority;
+mod proxy;
+mod publish;
+mod redact;
+mod saves;
+mod schedule;
+mod settings;
+mod storage;
+mod updates;
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::process::Child;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Mutex;
-use tauri::{async_runtime, AppHandle, Manager, RunEvent};
-use tauri_plugin_shell::{
-    process::{CommandChild, CommandEvent},
-    ShellExt,
-};
+use std::time::{Duration, Instant};
+use tauri::{async_runtime, AppHandle, Emitter, Manager, RunEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+
+// Target triple this launcher was built for, used to pick matching executables
+pub(crate) fn current_platform() -> &'static str {
+    env!("TAURI_ENV_TARGET_TRIPLE")
+}
+
+// How often tracked games are polled so finished ones get reaped
+const GAME_REAP_INTERVAL: Duration = Duration::from_secs(5);
+
+// A freshly written or chmod'd executable can briefly stay locked by the
+// writer, antivirus or indexer (Windows sharing violations, ETXTBSY on Unix),
+// so spawning is retried a few times before giving up
+const SPAWN_ATTEMPTS: u32 = 3;
+const SPAWN_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+#[cfg(windows)]
+const TRANSIENT_SPAWN_ERRORS: &[i32] = &[
+    32, // ERROR_SHARING_VIOLATION
+    33, // ERROR_LOCK_VIOLATION
+];
+#[cfg(unix)]
+const TRANSIENT_SPAWN_ERRORS: &[i32] = &[
+    16, // EBUSY
+    26, // ETXTBSY
+];
+
+fn is_transient_spawn_error(error: &std::io::Error) -> bool {
+    error
+        .raw_os_error()
+        .is_some_and(|code| TRANSIENT_SPAWN_ERRORS.contains(&code))
+}
+
+// Spawn the executable, retrying only on transient lock errors. The game's
+// save folder, when known, is passed in GAMEX_SAVE_DIR.
+async fn spawn_with_retry(path: &str, save_dir: Option<&Path>) -> std::io::Result<Child> {
+    let mut attempt = 1;
+    loop {
+        let mut command = std::process::Command::new(path);
+        if let Some(save_dir) = save_dir {
+            command.env(saves::SAVE_DIR_ENV, save_dir);
+        }
+        match command.spawn() {
+            Ok(child) => return Ok(child),
+            Err(e) if attempt < SPAWN_ATTEMPTS && is_transient_spawn_error(&e) => {
+                println!(
+                    "[Tauri] Spawn attempt {} failed ({}), retrying...",
+                    attempt, e
+                );
+                attempt += 1;
+                tokio::time::sleep(SPAWN_RETRY_DELAY).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct GameExited {
+    pid: u32,
+    exit_code: Option<i32>,
+}
+
+// A launched game and the executable it was started from
+struct GameProcess {
+    child: Child,
+    path: String,
+}
+
+#[derive(Serialize)]
+struct TrackedGame {
+    pid: u32,
+    path: String,
+}
+
+// Read-only view of what the launcher is tracking, for bug reports
+#[derive(Serialize)]
+struct ProcessManagerSnapshot {
+    games: Vec<TrackedGame>,
+    ipfs_tracked: bool,
+    ipfs_adopted: bool,
+    ipfs_pid: Option<u32>,
+    max_concurrent_games: Option<usize>,
+    ipfs_unavailable: Option<String>,
+}
+
+// A reserved launch slot; released on drop unless the game was spawned
+struct GameSlot<'a> {
+    manager: &'a ProcessManager,
+    filled: bool,
+}
+
+impl GameSlot<'_> {
+    fn fill(mut self, child: Child, path: &str) {
+        self.filled = true;
+        self.manager.add_game_process(child, path);
+    }
+}
+
+impl Drop for GameSlot<'_> {
+    fn drop(&mut self) {
+        if !self.filled {
+            self.manager.release_game_slot();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum IpfsBinaryStatus {
+    // Startup checksum still running
+    Pending,
+    Ok,
+    Failed,
+}
 
 // Process manager to track all spawned child processes
 struct ProcessManager {
-    game_processes: Mutex<Vec<Child>>,
+    game_processes: Mutex<Vec<GameProcess>>,
+    // Launches that passed the slot check but haven't spawned yet. Only
+    // changed while `game_processes` is locked, so the two always add up.
+    reserved_game_slots: AtomicUsize,
     ipfs_process: Mutex<Option<CommandChild>>,
+    // A daemon from a previous run was adopted instead of spawning our own;
+    // it's running but not ours to track or kill
+    ipfs_adopted: AtomicBool,
+    // Maximum number of games allowed to run at once (None = unlimited)
+    max_concurrent_games: Mutex<Option<usize>>,
+    // Why IPFS couldn't be started (e.g. sidecar missing from the bundle)
+    ipfs_unavailable: Mutex<Option<String>>,
+    // A daemon was already running on the API port at startup
+    ipfs_conflict: AtomicBool,
+    // Outcome of the bundled sidecar's startup checksum
+    ipfs_binary: Mutex<IpfsBinaryStatus>,
 }
 
 impl ProcessManager {
     fn new() -> Self {
         Self {
             game_processes: Mutex::new(Vec::new()),
+            reserved_game_slots: AtomicUsize::new(0),
             ipfs_process: Mutex::new(None),
+            ipfs_adopted: AtomicBool::new(false),
+            max_concurrent_games: Mutex::new(None),
+            ipfs_unavailable: Mutex::new(None),
+            ipfs_conflict: AtomicBool::new(false),
+            ipfs_binary: Mutex::new(IpfsBinaryStatus::Pending),
+        }
+    }
+
+    fn max_concurrent_games(&self) -> Option<usize> {
+        self.max_concurrent_games
+            .lock()
+            .map(|limit| *limit)
+            .unwrap_or(None)
+    }
+
+    fn set_max_concurrent_games(&self, limit: Option<usize>) {
+        if let Ok(mut max) = self.max_concurrent_games.lock() {
+            *max = limit;
+            println!("[ProcessManager] Max concurrent games set to {:?}", limit);
         }
     }
 
-    fn add_game_process(&self, child: Child) {
+    // Drop processes that have already exited, returning them. `try_wait`
+    // collects the exit status, so finished games don't linger as zombies.
+    fn reap_exited_games(&self) -> Vec<GameExited> {
+        let mut exited = Vec::new();
+
         if let Ok(mut processes) = self.game_processes.lock() {
-            processes.push(child);
-            println!(
-                "[ProcessManager] Added game process. Total tracked: {}",
-                processes.len()
-            );
+            processes.retain_mut(|game| match game.child.try_wait() {
+                Ok(Some(status)) => {
+                    exited.push(GameExited {
+                        pid: game.child.id(),
+                        exit_code: status.code(),
+                    });
+                    false
+                }
+                Ok(None) => true,
+                Err(e) => {
+                    eprintln!("[ProcessManager] Failed to poll game process: {}", e);
+                    false
+                }
+            });
+        }
+
+        exited
+    }
+
+    // Claim a slot for a launch that's about to spawn, or refuse when the
+    // limit is reached. Games still spawning count too, so concurrent
+    // launches (a scheduled one plus a click) can't both squeeze past the
+    // check. Dropping the slot without `fill` gives it back.
+    fn reserve_game_slot(&self) -> Result<GameSlot<'_>, String> {
+        let processes = self
+            .game_processes
+            .lock()
+            .map_err(|e| format!("Failed to check running games: {}", e))?;
+        let running = processes.len() + self.reserved_game_slots.load(Ordering::SeqCst);
+
+        if let Some(limit) = self.max_concurrent_games() {
+            if running >= limit {
+                println!(
+                    "[ProcessManager] Refusing launch: {} of {} games running",
+                    running, limit
+                );
+                return Err("Too many games running".to_string());
+            }
+        }
+
+        self.reserved_game_slots.fetch_add(1, Ordering::SeqCst);
+        Ok(GameSlot {
+            manager: self,
+            filled: false,
+        })
+    }
+
+    fn release_game_slot(&self) {
+        let _processes = self.game_processes.lock();
+        self.reserved_game_slots.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    // Track a spawned game in place of its reservation
+    fn add_game_process(&self, child: Child, path: &str) {
+        let mut processes = match self.game_processes.lock() {
+            Ok(processes) => processes,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        processes.push(GameProcess {
+            child,
+            path: path.to_string(),
+        });
+        self.reserved_game_slots.fetch_sub(1, Ordering::SeqCst);
+        println!(
+            "[ProcessManager] Added game process. Total tracked: {}",
+            processes.len()
+        );
+    }
+
+    fn tracks_game(&self, pid: u32) -> bool {
+        self.game_processes
+            .lock()
+            .map(|processes| processes.iter().any(|game| game.child.id() == pid))
+            .unwrap_or(false)
+    }
+
+    fn ipfs_running(&self) -> bool {
+        self.ipfs_adopted.load(Ordering::Relaxed)
+            || self
+                .ipfs_process
+                .lock()
+                .map(|ipfs| ipfs.is_some())
+                .unwrap_or(false)
+    }
+
+    fn set_ipfs_adopted(&self) {
+        self.ipfs_adopted.store(true, Ordering::Relaxed);
+        println!("[ProcessManager] Using adopted IPFS daemon");
+    }
+
+    fn ipfs_available(&self) -> bool {
+        self.ipfs_unavailable
+            .lock()
+            .map(|reason| reason.is_none())
+            .unwrap_or(false)
+    }
+
+    fn set_ipfs_unavailable(&self, reason: String) {
+        if let Ok(mut unavailable) = self.ipfs_unavailable.lock() {
+            *unavailable = Some(reason);
+        }
+    }
+
+    fn ipfs_conflict(&self) -> bool {
+        self.ipfs_conflict.load(Ordering::Relaxed)
+    }
+
+    fn set_ipfs_conflict(&self) {
+        self.ipfs_conflict.store(true, Ordering::Relaxed);
+    }
+
+    fn ipfs_binary_status(&self) -> IpfsBinaryStatus {
+        self.ipfs_binary
+            .lock()
+            .map(|status| *status)
+            .unwrap_or(IpfsBinaryStatus::Failed)
+    }
+
+    fn set_ipfs_binary_status(&self, status: IpfsBinaryStatus) {
+        if let Ok(mut binary) = self.ipfs_binary.lock() {
+            *binary = status;
+        }
+    }
+
+    // Every sidecar run goes through this, so a binary that failed (or hasn't
+    // finished) its checksum is never executed
+    fn ensure_ipfs_binary_ok(&self) -> Result<(), String> {
+        match self.ipfs_binary_status() {
+            IpfsBinaryStatus::Ok => Ok(()),
+            IpfsBinaryStatus::Pending => {
+                Err("IPFS binary is still being verified, try again shortly".to_string())
+            }
+            IpfsBinaryStatus::Failed => {
+                Err("IPFS binary failed its integrity check, reinstall GameX".to_string())
+            }
+        }
+    }
+
+    // Installs and publishing need the daemon; used to fail fast in limited mode
+    fn ensure_ipfs_available(&self) -> Result<(), String> {
+        match self.ipfs_unavailable.lock().ok().and_then(|reason| reason.clone()) {
+            Some(reason) => Err(format!("IPFS is unavailable: {}", reason)),
+            None => Ok(()),
+        }
+    }
+
+    // Forget the daemon handle once its process has exited
+    fn clear_ipfs_process(&self) {
+        if let Ok(mut ipfs) = self.ipfs_process.lock() {
+            *ipfs = None;
+        }
+    }
+
+    // The daemon exited and won't be restarted; installs and publishing fail
+    // fast from here on instead of hitting a dead API. Returns the reason.
+    fn set_ipfs_exited(&self, code: Option<i32>) -> String {
+        self.clear_ipfs_process();
+        let reason = match code {
+            Some(code) => format!("IPFS daemon exited with code {}", code),
+            None => "IPFS daemon was terminated".to_string(),
+        };
+        self.set_ipfs_unavailable(reason.clone());
+        reason
+    }
+
+    fn snapshot(&self) -> ProcessManagerSnapshot {
+        let games = self
+            .game_processes
+            .lock()
+            .map(|processes| {
+                processes
+                    .iter()
+                    .map(|game| TrackedGame {
+                        pid: game.child.id(),
+                        path: game.path.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let ipfs_pid = self
+            .ipfs_process
+            .lock()
+            .ok()
+            .and_then(|ipfs| ipfs.as_ref().map(|child| child.pid()));
+
+        ProcessManagerSnapshot {
+            games,
+            ipfs_tracked: ipfs_pid.is_some(),
+            ipfs_adopted: self.ipfs_adopted.load(Ordering::Relaxed),
+            ipfs_pid,
+            max_concurrent_games: self.max_concurrent_games(),
+            ipfs_unavailable: self.ipfs_unavailable.lock().ok().and_then(|reason| reason.clone()),
         }
     }
 
@@ -38,23 +401,36 @@ impl ProcessManager {
         }
     }
 
-    fn kill_all(&self) {
-        // Kill game processes
+    // Kill every tracked game, leaving the IPFS daemon running
+    fn kill_all_games(&self) -> Vec<GameExited> {
+        let mut killed = Vec::new();
+
         if let Ok(mut processes) = self.game_processes.lock() {
             println!(
                 "[ProcessManager] Killing {} game processes",
                 processes.len()
             );
-            for child in processes.iter_mut() {
+            for GameProcess { mut child, .. } in processes.drain(..) {
                 if let Err(e) = child.kill() {
                     eprintln!("[ProcessManager] Failed to kill game process: {}", e);
                 } else {
                     println!("[ProcessManager] Successfully killed game process");
                 }
+                // Reap the killed process so it doesn't become a zombie
+                let exit_code = child.wait().ok().and_then(|status| status.code());
+                killed.push(GameExited {
+                    pid: child.id(),
+                    exit_code,
+                });
             }
-            processes.clear();
         }
 
+        killed
+    }
+
+    fn kill_all(&self) {
+        self.kill_all_games();
+
         // Shutdown IPFS daemon by sending SIGTERM on Unix or taskkill on Windows
         if let Ok(mut ipfs) = self.ipfs_process.lock() {
             if let Some(child) = ipfs.take() {
@@ -114,61 +490,354 @@ impl ProcessManager {
     }
 }
 
+#[derive(Clone, Serialize)]
+struct IpfsUnavailable {
+    reason: String,
+}
+
+// Configure the repo and spawn the daemon sidecar
+async fn start_ipfs(
+    app_handle: &AppHandle,
+    settings: &settings::Settings,
+) -> Result<(async_runtime::Receiver<CommandEvent>, CommandChild), String> {
+    let ipfs_path = ipfs::ipfs_path(app_handle)?;
+    println!("[Tauri] IPFS_PATH: {:?}", ipfs_path.to_string_lossy().to_string());
+
+    println!("[Tauri] Creating folder if it does not exist...");
+    // Create the .ipfs directory if it doesn't exist
+    if !ipfs_path.exists() {
+        std::fs::create_dir_all(&ipfs_path)
+            .map_err(|e| format!("Failed to create IPFS_PATH: {}", e))?;
+        println!("[Tauri] Created IPFS_PATH directory at {:?}", ipfs_path.to_string_lossy().to_string());
+    }
+
+    // Create environment variables
+    let env = ipfs::ipfs_env(app_handle)?;
+
+    let sidecar = || ipfs::sidecar(app_handle);
+
+    // The first sidecar run is where a missing binary shows up
+    for (key, value) in ipfs::cors_commands(settings.ipfs_api_port) {
+        sidecar()?
+            .args(["config", key, &value, "--json"])
+            .envs(env.clone())
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run IPFS sidecar: {}", e))?;
+    }
+
+    for (key, value) in ipfs::address_commands(settings) {
+        sidecar()?
+            .args(["config", key, &value])
+            .envs(env.clone())
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run IPFS sidecar: {}", e))?;
+    }
+
+    // Spawn IPFS daemon
+    println!("[Tauri] Starting IPFS daemon...");
+    let mut daemon_args = vec!["daemon", "--init"];
+    if settings.offline_mode {
+        println!("[Tauri] Offline mode enabled, starting daemon with --offline");
+        daemon_args.push("--offline");
+    }
+
+    sidecar()?
+        .args(daemon_args)
+        .envs(env)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn IPFS daemon: {}", e))
+}
+
+// False when IPFS couldn't be started; installs and publishing are disabled then
 #[tauri::command]
-fn execute_game(path: String, app_handle: AppHandle) -> Result<String, String> {
+fn ipfs_available(app_handle: AppHandle) -> bool {
+    app_handle.state::<ProcessManager>().ipfs_available()
+}
+
+// True when a daemon from a previous run was found at startup (adopted or
+// shut down depending on `adopt_existing_daemon`)
+#[tauri::command]
+fn ipfs_daemon_conflict(app_handle: AppHandle) -> bool {
+    app_handle.state::<ProcessManager>().ipfs_conflict()
+}
+
+// Whether the bundled IPFS binary matched its build-time checksum: pending
+// while the startup check runs, then ok or failed
+#[tauri::command]
+fn ipfs_binary_status(app_handle: AppHandle) -> IpfsBinaryStatus {
+    app_handle.state::<ProcessManager>().ipfs_binary_status()
+}
+
+// Snapshot of tracked processes for support; exited games are reaped first
+#[tauri::command]
+fn process_manager_debug(app_handle: AppHandle) -> ProcessManagerSnapshot {
+    reap_games(&app_handle);
+    app_handle.state::<ProcessManager>().snapshot()
+}
+
+// Reap finished games and notify the frontend that they stopped
+fn reap_games(app_handle: &AppHandle) {
+    for exited in app_handle.state::<ProcessManager>().reap_exited_games() {
+        println!(
+            "[ProcessManager] Game process {} exited with {:?}",
+            exited.pid, exited.exit_code
+        );
+        let _ = app_handle.emit("game-exited", exited);
+    }
+}
+
+// Deprioritize (or restore) a running game so the launcher stays responsive.
+// Only games the launcher started can be changed.
+#[tauri::command]
+fn set_game_priority(pid: u32, priority: String, app_handle: AppHandle) -> Result<(), String> {
+    let priority = priority::Priority::parse(&priority)?;
+
+    // Drop exited games first so a recycled pid isn't mistaken for one
+    reap_games(&app_handle);
+    if !app_handle.state::<ProcessManager>().tracks_game(pid) {
+        return Err(format!("Process {} is not a running game", pid));
+    }
+
+    priority::set_process_priority(pid, priority)?;
+    println!("[ProcessManager] Set game {} priority to {:?}", pid, priority);
+    Ok(())
+}
+
+// Stop every running game but keep the launcher (and IPFS) up
+#[tauri::command]
+fn stop_all_games(app_handle: AppHandle) -> Result<usize, String> {
+    let killed = app_handle.state::<ProcessManager>().kill_all_games();
+    let count = killed.len();
+
+    for exited in killed {
+        let _ = app_handle.emit("game-exited", exited);
+    }
+
+    println!("[ProcessManager] Stopped {} game(s)", count);
+    Ok(count)
+}
+
+// Launch a game. Installed games start straight from disk with no IPFS
+// dependency; pass `require_ipfs` only when the game itself needs the daemon.
+// With `expected_platform`, binaries built for another OS or CPU are refused.
+// Successful launches are added to the launch history.
+#[tauri::command]
+async fn execute_game(
+    path: String,
+    game_id: Option<String>,
+    launch_kind: Option<api::LaunchKind>,
+    expected_sha256: Option<String>,
+    require_ipfs: Option<bool>,
+    expected_platform: Option<String>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let message =
+        launch_game(
+            &path,
+            game_id.as_deref(),
+            launch_kind,
+            expected_sha256,
+            require_ipfs,
+            expected_platform.as_deref(),
+            &app_handle,
+        )
+        .await?;
+
+    if let Err(e) = history::record_launch(&app_handle, &path, game_id) {
+        eprintln!("[Tauri] Failed to record launch history: {}", e);
+    }
+    Ok(message)
+}
+
+async fn launch_game(
+    path: &str,
+    game_id: Option<&str>,
+    launch_kind: Option<api::LaunchKind>,
+    expected_sha256: Option<String>,
+    require_ipfs: Option<bool>,
+    expected_platform: Option<&str>,
+    app_handle: &AppHandle,
+) -> Result<String, String> {
     println!("[Tauri] Executing game at path: {}", path);
 
-    let process_manager = app_handle.state::<ProcessManager>();
+    if require_ipfs.unwrap_or(false) && !app_handle.state::<ProcessManager>().ipfs_running() {
+        return Err("IPFS daemon is not running".to_string());
+    }
 
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        let child = Command::new(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to execute game: {}", e))?;
+    // Store launchers (steam://...) are opened by the OS, not spawned or tracked
+    if launch_kind == Some(api::LaunchKind::Uri) {
+        app_handle
+            .opener()
+            .open_url(path, None::<&str>)
+            .map_err(|e| format!("Failed to open launch URI: {}", e))?;
+        return Ok(format!("Game launched via URI: {}", path));
+    }
+
+    // A macOS `.app` bundle is launched through the binary inside it
+    let executable = platform::resolve_executable(Path::new(path));
+    if !executable.is_file() {
+        return Err(format!("Executable not found: {}", path));
+    }
+    let executable = executable.to_string_lossy().into_owned();
 
-        process_manager.add_game_process(child);
-        Ok(format!("Game launched: {}", path))
+    if let Some(expected_platform) = expected_platform {
+        platform::ensure_platform_match(&executable, expected_platform).await?;
     }
 
-    #[cfg(target_os = "macos")]
+    if let Some(expected) = expected_sha256 {
+        if !integrity::sha256_matches(PathBuf::from(&executable), &expected).await? {
+            return Err("Executable checksum mismatch, verify or reinstall the game".to_string());
+        }
+    }
+
+    reap_games(app_handle);
+    let process_manager = app_handle.state::<ProcessManager>();
+    let slot = process_manager.reserve_game_slot()?;
+
+    // A missing save folder shouldn't stop the game from starting
+    let save_dir = game_id.and_then(|game_id| {
+        saves::save_dir(app_handle, game_id)
+            .map_err(|e| eprintln!("[Tauri] No save folder for {}: {}", game_id, e))
+            .ok()
+    });
+
+    make_executable(&executable)?;
+
+    let child = spawn_with_retry(&executable, save_dir.as_deref())
+        .await
+        .map_err(|e| format!("Failed to execute game: {}", e))?;
+
+    slot.fill(child, path);
+    Ok(format!("Game launched: {}", path))
+}
+
+// Set the +x bit before spawning; Windows has no execute permission
+fn make_executable(path: &str) -> Result<(), String> {
+    #[cfg(unix)]
     {
         use std::process::Command;
-        // On macOS, we need to make the file executable first
-        Command::new("chmod")
-            .args(["+x", &path])
+        let output = Command::new("chmod")
+            .args(["+x", path])
             .output()
             .map_err(|e| format!("Failed to set executable permission: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to set executable permission: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
 
-        let child = Command::new(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to execute game: {}", e))?;
+// Maintenance after restoring a backup, which can drop the +x bit. Returns the
+// paths that couldn't be fixed.
+#[tauri::command]
+async fn fix_permissions(paths: Vec<String>) -> Result<Vec<String>, String> {
+    async_runtime::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .filter(|path| match make_executable(path) {
+                Ok(()) => false,
+                Err(e) => {
+                    eprintln!("[Tauri] {}: {}", path, e);
+                    true
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Permission task failed: {}", e))
+}
 
-        process_manager.add_game_process(child);
-        Ok(format!("Game launched: {}", path))
-    }
+#[derive(Serialize)]
+struct LaunchTest {
+    started: bool,
+    exited: bool,
+    exit_code: Option<i32>,
+}
 
-    #[cfg(target_os = "linux")]
-    {
+// Spawn a build briefly to confirm it runs; the process is never tracked by ProcessManager
+#[tauri::command]
+async fn test_launch(path: String, timeout_ms: u64) -> Result<LaunchTest, String> {
+    println!("[Tauri] Test launching: {}", path);
+
+    async_runtime::spawn_blocking(move || {
         use std::process::Command;
-        // On Linux, we need to make the file executable first
-        Command::new("chmod")
-            .args(["+x", &path])
-            .output()
-            .map_err(|e| format!("Failed to set executable permission: {}", e))?;
 
-        let child = Command::new(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to execute game: {}", e))?;
+        make_executable(&path)?;
+
+        let mut child = match Command::new(&path).spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                println!("[Tauri] Test launch failed to start: {}", e);
+                return Ok(LaunchTest {
+                    started: false,
+                    exited: false,
+                    exit_code: None,
+                });
+            }
+        };
 
-        process_manager.add_game_process(child);
-        Ok(format!("Game launched: {}", path))
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    return Ok(LaunchTest {
+                        started: true,
+                        exited: true,
+                        exit_code: status.code(),
+                    });
+                }
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Ok(None) => break,
+                Err(e) => return Err(format!("Failed to poll test process: {}", e)),
+            }
+        }
+
+        // Still running at the deadline: it started fine, so stop it
+        let _ = child.kill();
+        let _ = child.wait();
+        Ok(LaunchTest {
+            started: true,
+            exited: false,
+            exit_code: None,
+        })
+    })
+    .await
+    .map_err(|e| format!("Test launch task failed: {}", e))?
+}
+
+#[tauri::command]
+fn get_max_concurrent_games(app_handle: AppHandle) -> Option<usize> {
+    app_handle.state::<ProcessManager>().max_concurrent_games()
+}
+
+#[tauri::command]
+fn set_max_concurrent_games(limit: Option<usize>, app_handle: AppHandle) -> Result<(), String> {
+    if limit == Some(0) {
+        return Err("Limit must be at least 1".to_string());
     }
+
+    settings::update(&app_handle, |settings| settings.max_concurrent_games = limit)?;
+    Ok(())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Registered first so a second launch exits before anything else
+        // starts; its gamex:// link is forwarded to this instance
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            deep_link::focus_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_http::init())
@@ -176,62 +845,214 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
         .manage(ProcessManager::new())
-        .invoke_handler(tauri::generate_handler![execute_game])
+        .manage(http::HttpClient::from_env())
+        .register_asynchronous_uri_scheme_protocol(proxy::PROXY_SCHEME, |ctx, request, responder| {
+            let client = http::proxy_client(ctx.app_handle());
+            let api_url = settings::current(ctx.app_handle()).ipfs_api_url();
+            async_runtime::spawn(async move {
+                responder.respond(proxy::handle(client, api_url, request).await);
+            });
+        })
+        .invoke_handler(tauri::generate_handler![
+            execute_game,
+            fix_permissions,
+            test_launch,
+            stop_all_games,
+            set_game_priority,
+            process_manager_debug,
+            history::launch_history,
+            ipfs_available,
+            ipfs_daemon_conflict,
+            ipfs_binary_status,
+            ipfs::ensure_ipfs_cors,
+            ipfs::resolve_executable_url,
+            ipfs::ipfs_bandwidth,
+            ipfs::estimate_download,
+            ipfs::ipfs_selftest,
+            ipfs::ipfs_nat_status,
+            ipfs::ipfs_set_network,
+            ipfs::ipfs_version_info,
+            ipfs::open_ipfs_webui,
+            ipfs::pin_cid,
+            ipfs::clear_ipfs_lock,
+            ipfs::benchmark_gateways,
+            ipfs::get_ipfs_routing,
+            ipfs::set_ipfs_routing,
+            ipfs::prune_orphan_pins,
+            ipfs::get_bootstrap_peers,
+            ipfs::set_bootstrap_peers,
+            ipfs_logs::ipfs_logs_filtered,
+            download::download_cid,
+            download::update_installed,
+            download::create_install_dirs,
+            auth::make_download_request,
+            auth::attach_signature,
+            updates::check_updates,
+            publish::publish_game,
+            library::export_library,
+            library::import_library,
+            library::scan_install_root,
+            storage::disk_space,
+            storage::library_storage,
+            storage::validate_install_dir,
+            saves::open_save_folder,
+            deep_link::take_pending_deep_link,
+            schedule::schedule_launch,
+            schedule::cancel_scheduled_launch,
+            integrity::is_installed,
+            integrity::verify_installed,
+            platform::check_platform_match,
+            integrity::verify_installed_dir,
+            settings::get_settings,
+            settings::set_settings,
+            api::api_base_url,
+            api::set_api_base_url,
+            catalog::sync_catalog,
+            get_max_concurrent_games,
+            set_max_concurrent_games
+        ])
         .setup(|app| {
-            println!("[Tauri] Initializing IPFS...");
+            let settings_state = settings::SettingsState::load(app.handle());
+            let startup_settings = settings_state.get();
+            app.manage(settings_state);
+            app.manage(api::ApiConfig::load(&startup_settings));
+            app.manage(ipfs_logs::IpfsLogs::default());
+            app.manage(catalog::CatalogSync::default());
+            app.manage(deep_link::PendingDeepLink::default());
+            app.manage(schedule::ScheduledLaunches::default());
 
-            let app_handle = app.handle().clone();
+            // Installers register the scheme on Windows and Linux; register it
+            // at runtime too so dev builds and AppImages handle links
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                eprintln!("[DeepLink] Failed to register {}:// links: {}", deep_link::SCHEME, e);
+            }
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                deep_link::handle_urls(app.handle(), urls.iter().map(|url| url.as_str()));
+            }
+            let link_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                deep_link::handle_urls(&link_handle, event.urls().iter().map(|url| url.as_str()));
+            });
+            settings::apply(app.handle(), &startup_settings);
 
-            async_runtime::spawn(async move {
-                // Get app data directory
-                let app_data_dir = app_handle.path().app_data_dir().unwrap();
-                let ipfs_path = app_data_dir.join(".ipfs");
-                println!("[Tauri] IPFS_PATH: {:?}", ipfs_path.to_string_lossy().to_string());
-
-                println!("[Tauri] Creating folder if it does not exist...");
-                // Create the .ipfs directory if it doesn't exist
-                if !ipfs_path.exists() {
-                    std::fs::create_dir_all(&ipfs_path).unwrap();
-                    println!("[Tauri] Created IPFS_PATH directory at {:?}", ipfs_path.to_string_lossy().to_string());
-                }
+            // Periodically reap games that exited on their own, so long
+            // launcher sessions don't accumulate zombie processes
+            let reaper_handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(GAME_REAP_INTERVAL);
+                reap_games(&reaper_handle);
+            });
 
-                // Create environment variables
-                let mut env = HashMap::new();
-                env.insert("IPFS_PATH".to_string(), ipfs_path.to_string_lossy().to_string());
+            let version_handle = app.handle().clone();
+            async_runtime::spawn(async move {
+                api::check_client_version(&version_handle).await;
+            });
 
-                let shell = app_handle.shell();
+            println!("[Tauri] Initializing IPFS...");
 
-                let cors_commands = vec![
-                    vec!["config", "API.HTTPHeaders.Access-Control-Allow-Origin", "[\"http://localhost:3000\", \"http://localhost:1420\", \"tauri://localhost\", \"https://webui.ipfs.io\", \"http://127.0.0.1:5001\"]", "--json"],
-                    vec!["config", "API.HTTPHeaders.Access-Control-Allow-Methods", "[\"PUT\", \"POST\", \"GET\"]", "--json"],
-                ];
+            let app_handle = app.handle().clone();
 
-                for cors_rule in cors_commands {
-                    shell.sidecar("ipfs").unwrap().args(cors_rule).envs(env.clone()).output().await.unwrap();
+            async_runtime::spawn(async move {
+                // Never run a sidecar that was replaced or corrupted after the
+                // build; checked first, since adopting a daemon runs it too
+                let process_manager = app_handle.state::<ProcessManager>();
+                if let Err(e) = integrity::verify_ipfs_sidecar().await {
+                    eprintln!("[Tauri] Refusing to start IPFS: {}", e);
+                    process_manager.set_ipfs_binary_status(IpfsBinaryStatus::Failed);
+                    process_manager.set_ipfs_unavailable(e.clone());
+                    let _ = app_handle.emit("ipfs-integrity-failed", IpfsUnavailable { reason: e });
+                    return;
                 }
+                process_manager.set_ipfs_binary_status(IpfsBinaryStatus::Ok);
 
-                // Spawn IPFS daemon
-                println!("[Tauri] Starting IPFS daemon...");
-                let sidecar_command = shell.sidecar("ipfs").unwrap()
-                    .args(["daemon",
-                    // "--offline",
-                    "--init"])
-                    .envs(env);
+                // A daemon left over from a previous run holds the ports, so
+                // spawning another would fail; adopt it or shut it down first
+                let client = http::client(&app_handle);
+                let api_url = startup_settings.ipfs_api_url();
+                if ipfs::daemon_reachable(&client, &api_url).await {
+                    app_handle.state::<ProcessManager>().set_ipfs_conflict();
+                    if startup_settings.adopt_existing_daemon {
+                        println!("[Tauri] Adopting IPFS daemon already running on {}", api_url);
+                        app_handle.state::<ProcessManager>().set_ipfs_adopted();
+                        // The daemon may predate the current CORS config; repo
+                        // changes apply the next time it starts
+                        match ipfs::ensure_ipfs_cors(app_handle.clone()).await {
+                            Ok(changed) if !changed.is_empty() => println!(
+                                "[Tauri] Updated CORS config of adopted daemon: {:?}",
+                                changed
+                            ),
+                            Ok(_) => {}
+                            Err(e) => eprintln!("[Tauri] Failed to check adopted daemon CORS: {}", e),
+                        }
+                        return;
+                    }
+                    println!("[Tauri] Shutting down IPFS daemon from a previous run...");
+                    if let Err(e) = ipfs::shutdown_daemon(&client, &api_url).await {
+                        eprintln!("[Tauri] {}", e);
+                    }
+                }
 
-                match sidecar_command.spawn() {
-                    Ok((mut rx, child)) => {
-                        println!("[Tauri] IPFS daemon spawned successfully!");
-                        let process_manager = app_handle.state::<ProcessManager>();
-                        process_manager.set_ipfs_process(child);
+                // A crash can leave a stale repo.lock behind; it's cleared and
+                // the daemon retried once if nothing else holds it
+                let mut retried_lock = false;
+                loop {
+                    match start_ipfs(&app_handle, &startup_settings).await {
+                        Ok((mut rx, child)) => {
+                            println!("[Tauri] IPFS daemon spawned successfully!");
+                            let process_manager = app_handle.state::<ProcessManager>();
+                            process_manager.set_ipfs_process(child);
 
+                            let logs = app_handle.state::<ipfs_logs::IpfsLogs>();
+                            let mut lock_held = false;
+                            let mut exit_code = None;
+                            while let Some(event) = rx.recv().await {
+                                match event {
+                                    CommandEvent::Stdout(line) => {
+                                        let line = String::from_utf8_lossy(&line);
+                                        logs.push(&line);
+                                        println!("[IPFS]: {}", line);
+                                    }
+                                    CommandEvent::Stderr(line) => {
+                                        let line = String::from_utf8_lossy(&line);
+                                        logs.push(&line);
+                                        lock_held |= ipfs::is_repo_lock_error(&line);
+                                        eprintln!("[IPFS]: {}", line);
+                                    }
+                                    CommandEvent::Terminated(status) => {
+                                        println!("[Tauri] IPFS daemon exited: {:?}", status.code);
+                                        exit_code = status.code;
+                                        break;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            process_manager.clear_ipfs_process();
 
-                        while let Some(event) = rx.recv().await {
-                            if let CommandEvent::Stdout(line) = event {
-                                println!("[IPFS]: {}", String::from_utf8(line).unwrap());
+                            if lock_held && !retried_lock {
+                                retried_lock = true;
+                                match ipfs::clear_stale_lock(&app_handle).await {
+                                    Ok(true) => {
+                                        println!("[Tauri] Removed stale IPFS repo lock, retrying daemon start");
+                                        continue;
+                                    }
+                                    Ok(false) => {}
+                                    Err(e) => eprintln!("[Tauri] Not clearing IPFS repo lock: {}", e),
+                                }
                             }
+
+                            let reason = process_manager.set_ipfs_exited(exit_code);
+                            eprintln!("[Tauri] {}, continuing in limited mode", reason);
+                            let _ = app_handle.emit("ipfs-unavailable", IpfsUnavailable { reason });
+                        },
+                        // Keep the launcher usable (installed games still launch) without IPFS
+                        Err(e) => {
+                            eprintln!("[Tauri] IPFS unavailable, continuing in limited mode: {}", e);
+                            app_handle.state::<ProcessManager>().set_ipfs_unavailable(e.clone());
+                            let _ = app_handle.emit("ipfs-unavailable", IpfsUnavailable { reason: e });
                         }
-                    },
-                    Err(e) => println!("[Tauri] Failed to spawn IPFS daemon: {}", e),
+                    }
+                    break;
                 }
             });
 
@@ -242,9 +1063,118 @@ pub fn run() {
         .run(|app_handle, event| {
             if let RunEvent::ExitRequested { .. } = event {
                 println!("[Tauri] Exit requested, cleaning up processes...");
+                app_handle.state::<schedule::ScheduledLaunches>().cancel_all();
                 let process_manager = app_handle.state::<ProcessManager>();
                 process_manager.kill_all();
                 println!("[Tauri] All processes cleaned up, exiting...");
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_launch_past_the_limit() {
+        let manager = ProcessManager::new();
+        manager.set_max_concurrent_games(Some(2));
+
+        let first = manager.reserve_game_slot().expect("first slot");
+        let _second = manager.reserve_game_slot().expect("second slot");
+        assert!(manager.reserve_game_slot().is_err());
+
+        // A failed spawn gives its slot back
+        drop(first);
+        assert!(manager.reserve_game_slot().is_ok());
+    }
+
+    #[test]
+    fn unlimited_without_a_limit() {
+        let manager = ProcessManager::new();
+        let slots: Vec<_> = (0..16).map(|_| manager.reserve_game_slot()).collect();
+        assert!(slots.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn concurrent_launches_stay_within_the_limit() {
+        let manager = ProcessManager::new();
+        manager.set_max_concurrent_games(Some(3));
+
+        let granted = AtomicUsize::new(0);
+        let barrier = std::sync::Barrier::new(8);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    barrier.wait();
+                    if let Ok(slot) = manager.reserve_game_slot() {
+                        granted.fetch_add(1, Ordering::SeqCst);
+                        // Hold the slot until every thread has tried
+                        barrier.wait();
+                        drop(slot);
+                    } else {
+                        barrier.wait();
+                    }
+                });
+            }
+        });
+        assert_eq!(granted.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn ipfs_binary_is_refused_until_verified() {
+        let manager = ProcessManager::new();
+        assert_eq!(manager.ipfs_binary_status(), IpfsBinaryStatus::Pending);
+        assert!(manager.ensure_ipfs_binary_ok().is_err());
+
+        manager.set_ipfs_binary_status(IpfsBinaryStatus::Ok);
+        assert!(manager.ensure_ipfs_binary_ok().is_ok());
+
+        manager.set_ipfs_binary_status(IpfsBinaryStatus::Failed);
+        assert!(manager.ensure_ipfs_binary_ok().is_err());
+    }
+
+    #[test]
+    fn exited_daemon_leaves_ipfs_unavailable() {
+        let manager = ProcessManager::new();
+        assert!(manager.ensure_ipfs_available().is_ok());
+
+        let reason = manager.set_ipfs_exited(Some(1));
+        assert_eq!(reason, "IPFS daemon exited with code 1");
+        assert!(!manager.ipfs_available());
+        assert!(!manager.ipfs_running());
+        assert_eq!(
+            manager.ensure_ipfs_available(),
+            Err("IPFS is unavailable: IPFS daemon exited with code 1".to_string())
+        );
+        assert_eq!(manager.set_ipfs_exited(None), "IPFS daemon was terminated");
+    }
+
+    #[test]
+    fn adopted_daemon_counts_as_running() {
+        let manager = ProcessManager::new();
+        assert!(!manager.ipfs_running());
+        manager.set_ipfs_adopted();
+        assert!(manager.ipfs_running());
+        assert!(manager.snapshot().ipfs_adopted);
+        // Not ours: nothing is tracked by pid
+        assert!(!manager.snapshot().ipfs_tracked);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn running_games_count_against_the_limit() {
+        let manager = ProcessManager::new();
+        manager.set_max_concurrent_games(Some(1));
+
+        let child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        manager.reserve_game_slot().expect("slot").fill(child, "sleep");
+        assert!(manager.reserve_game_slot().is_err());
+
+        assert_eq!(manager.kill_all_games().len(), 1);
+        assert!(manager.reserve_game_slot().is_ok());
+    }
+}
@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Format version written into library exports
+const LIBRARY_EXPORT_VERSION: u32 = 1;
+
+// Manifest dropped into each install dir, and how deep scans look for it
+pub const MANIFEST_FILE: &str = "gamex.json";
+const MAX_SCAN_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledGame {
+    pub game_id: String,
+    pub current_cid: String,
+    // Path to the installed executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_dir: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LibraryExport {
+    version: u32,
+    games: Vec<InstalledGame>,
+}
+
+#[derive(Serialize)]
+pub struct LibraryImport {
+    pub games: Vec<InstalledGame>,
+    // Ids of entries whose executable or install dir no longer exists
+    pub missing: Vec<String>,
+}
+
+fn path_missing(path: &Option<String>) -> bool {
+    path.as_deref()
+        .is_some_and(|path| !Path::new(path).exists())
+}
+
+#[tauri::command]
+pub fn export_library(installed: Vec<InstalledGame>, dest: String) -> Result<(), String> {
+    let export = LibraryExport {
+        version: LIBRARY_EXPORT_VERSION,
+        games: installed,
+    };
+
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize library: {}", e))?;
+    std::fs::write(&dest, json).map_err(|e| format!("Failed to write {}: {}", dest, e))?;
+
+    println!(
+        "[Library] Exported {} game(s) to {}",
+        export.games.len(),
+        dest
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub fn import_library(src: String) -> Result<LibraryImport, String> {
+    let json =
+        std::fs::read_to_string(&src).map_err(|e| format!("Failed to read {}: {}", src, e))?;
+    let export: LibraryExport =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid library file: {}", e))?;
+
+    if export.version > LIBRARY_EXPORT_VERSION {
+        return Err(format!(
+            "Library file version {} is newer than supported version {}",
+            export.version, LIBRARY_EXPORT_VERSION
+        ));
+    }
+
+    let missing: Vec<String> = export
+        .games
+        .iter()
+        .filter(|game| path_missing(&game.path) || path_missing(&game.install_dir))
+        .map(|game| game.game_id.clone())
+        .collect();
+
+    println!(
+        "[Library] Imported {} game(s), {} missing on disk",
+        export.games.len(),
+        missing.len()
+    );
+
+    Ok(LibraryImport {
+        games: export.games,
+        missing,
+    })
+}
+
+// Contents of an install dir's gamex.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameManifest {
+    pub game_id: String,
+    pub cid: String,
+    // Executable path relative to the install dir
+    pub executable: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FoundGame {
+    pub manifest: GameManifest,
+    pub install_dir: String,
+    // Absolute executable path; None when the manifest points at a missing file
+    pub path: Option<String>,
+}
+
+fn read_manifest(dir: &Path) -> Option<FoundGame> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    if !manifest_path.is_file() {
+        return None;
+    }
+
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str::<GameManifest>(&json).map_err(|e| e.to_string()));
+
+    match manifest {
+        Ok(manifest) => {
+            let executable = dir.join(&manifest.executable);
+            Some(FoundGame {
+                path: executable
+                    .is_file()
+                    .then(|| executable.to_string_lossy().to_string()),
+                install_dir: dir.to_string_lossy().to_string(),
+                manifest,
+            })
+        }
+        Err(e) => {
+            eprintln!(
+                "[Library] Skipping invalid manifest {:?}: {}",
+                manifest_path, e
+            );
+            None
+        }
+    }
+}
+
+// Find installs made outside the launcher by looking for manifests under
+// `root`. Unreadable entries are skipped; only an unreadable root is an error.
+#[tauri::command]
+pub fn scan_install_root(root: String) -> Result<Vec<FoundGame>, String> {
+    let root_path = PathBuf::from(&root);
+    std::fs::read_dir(&root_path).map_err(|e| format!("Failed to read {}: {}", root, e))?;
+
+    let mut found = Vec::new();
+    let mut pending = vec![(root_path, 0usize)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        // A game's own subfolders aren't searched for further manifests
+        if let Some(game) = read_manifest(&dir) {
+            found.push(game);
+            continue;
+        }
+        if depth >= MAX_SCAN_DEPTH {
+            continue;
+        }
+
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("[Library] Skipping {:?}: {}", dir, e);
+                continue;
+            }
+        };
+
+        for entry in entries {
+            match entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))) {
+                // Symlinks aren't followed so loops can't trap the scan
+                Ok((path, file_type)) if file_type.is_dir() => pending.push((path, depth + 1)),
+                Ok(_) => {}
+                Err(e) => eprintln!("[Library] Skipping entry in {:?}: {}", dir, e),
+            }
+        }
+    }
+
+    println!("[Library] Found {} install(s) under {}", found.len(), root);
+    Ok(found)
+}
@@ -0,0 +1,540 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tauri::async_runtime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Os {
+    Windows,
+    Linux,
+    Mac,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Pe,
+    Elf,
+    MachO,
+    // `#!` scripts run on any unix
+    Script,
+}
+
+impl Format {
+    fn runs_on(self, os: Os) -> bool {
+        match self {
+            Format::Pe => os == Os::Windows,
+            Format::Elf => os == Os::Linux,
+            Format::MachO => os == Os::Mac,
+            Format::Script => os != Os::Windows,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Pe => "a Windows (PE)",
+            Format::Elf => "a Linux (ELF)",
+            Format::MachO => "a macOS (Mach-O)",
+            Format::Script => "a script",
+        })
+    }
+}
+
+// What the file header says. `arch` is None for scripts, universal Mach-O
+// binaries and machine types we don't know.
+#[derive(Debug)]
+struct BinaryInfo {
+    format: Format,
+    arch: Option<Arch>,
+}
+
+// `expected_platform` is either a bare OS (windows, linux, macos) or a
+// target triple like the API's x86_64-pc-windows-msvc
+fn parse_platform(platform: &str) -> Result<(Os, Option<Arch>), String> {
+    let platform = platform.trim().to_ascii_lowercase();
+    let os = if platform.contains("windows") {
+        Os::Windows
+    } else if platform.contains("linux") {
+        Os::Linux
+    } else if ["macos", "darwin", "apple"]
+        .iter()
+        .any(|name| platform.contains(name))
+    {
+        Os::Mac
+    } else {
+        return Err(format!("Unknown platform: {}", platform));
+    };
+
+    let arch = match platform.split('-').next().unwrap_or_default() {
+        "x86_64" | "amd64" => Some(Arch::X86_64),
+        "i386" | "i586" | "i686" | "x86" => Some(Arch::X86),
+        "aarch64" | "arm64" => Some(Arch::Aarch64),
+        cpu if cpu.starts_with("arm") || cpu.starts_with("thumb") => Some(Arch::Arm),
+        _ => None,
+    };
+    Ok((os, arch))
+}
+
+fn read_at(file: &mut File, offset: u64, buffer: &mut [u8]) -> bool {
+    file.seek(SeekFrom::Start(offset)).is_ok() && file.read_exact(buffer).is_ok()
+}
+
+fn elf_arch(machine: u16) -> Option<Arch> {
+    match machine {
+        0x03 => Some(Arch::X86),
+        0x3e => Some(Arch::X86_64),
+        0x28 => Some(Arch::Arm),
+        0xb7 => Some(Arch::Aarch64),
+        _ => None,
+    }
+}
+
+fn pe_arch(machine: u16) -> Option<Arch> {
+    match machine {
+        0x014c => Some(Arch::X86),
+        0x8664 => Some(Arch::X86_64),
+        0x01c0 | 0x01c4 => Some(Arch::Arm),
+        0xaa64 => Some(Arch::Aarch64),
+        _ => None,
+    }
+}
+
+fn mach_o_arch(cpu_type: u32) -> Option<Arch> {
+    match cpu_type {
+        7 => Some(Arch::X86),
+        0x0100_0007 => Some(Arch::X86_64),
+        12 => Some(Arch::Arm),
+        0x0100_000c => Some(Arch::Aarch64),
+        _ => None,
+    }
+}
+
+// Identify the executable format and architecture from its magic bytes. Only
+// the headers are read, never the whole file.
+fn inspect(path: &Path) -> Result<Option<BinaryInfo>, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut header = [0u8; 64];
+    let read = file
+        .read(&mut header)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let header = &header[..read];
+
+    if header.starts_with(b"\x7fELF") && header.len() >= 20 {
+        let machine = [header[18], header[19]];
+        // EI_DATA: 2 is big-endian, anything else is treated as little-endian
+        let machine = if header[5] == 2 {
+            u16::from_be_bytes(machine)
+        } else {
+            u16::from_le_bytes(machine)
+        };
+        return Ok(Some(BinaryInfo {
+            format: Format::Elf,
+            arch: elf_arch(machine),
+        }));
+    }
+
+    if header.starts_with(b"MZ") && header.len() >= 0x40 {
+        let pe_offset =
+            u32::from_le_bytes([header[0x3c], header[0x3d], header[0x3e], header[0x3f]]);
+        let mut pe_header = [0u8; 6];
+        if !read_at(&mut file, pe_offset as u64, &mut pe_header) || &pe_header[..4] != b"PE\0\0" {
+            // A DOS stub without a PE header isn't a Windows executable
+            return Ok(None);
+        }
+        return Ok(Some(BinaryInfo {
+            format: Format::Pe,
+            arch: pe_arch(u16::from_le_bytes([pe_header[4], pe_header[5]])),
+        }));
+    }
+
+    if header.len() >= 8 {
+        let magic = [header[0], header[1], header[2], header[3]];
+        let cpu_type = [header[4], header[5], header[6], header[7]];
+        match magic {
+            [0xfe, 0xed, 0xfa, 0xce | 0xcf] => {
+                return Ok(Some(BinaryInfo {
+                    format: Format::MachO,
+                    arch: mach_o_arch(u32::from_be_bytes(cpu_type)),
+                }))
+            }
+            [0xce | 0xcf, 0xfa, 0xed, 0xfe] => {
+                return Ok(Some(BinaryInfo {
+                    format: Format::MachO,
+                    arch: mach_o_arch(u32::from_le_bytes(cpu_type)),
+                }))
+            }
+            // Universal binary; Java class files share the magic but have a
+            // large version number where the slice count would be
+            [0xca, 0xfe, 0xba, 0xbe] if u32::from_be_bytes(cpu_type) < 32 => {
+                return Ok(Some(BinaryInfo {
+                    format: Format::MachO,
+                    arch: None,
+                }))
+            }
+            _ => {}
+        }
+    }
+
+    if header.starts_with(b"#!") {
+        return Ok(Some(BinaryInfo {
+            format: Format::Script,
+            arch: None,
+        }));
+    }
+
+    Ok(None)
+}
+
+// The `<string>` value of `key` in an XML property list. Binary plists aren't
+// handled; callers fall back to the bundle name.
+fn plist_string(plist: &str, key: &str) -> Option<String> {
+    let (_, rest) = plist.split_once(&format!("<key>{}</key>", key))?;
+    let value = rest.trim_start().strip_prefix("<string>")?;
+    let (value, _) = value.split_once("</string>")?;
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+// The binary inside a macOS `.app` bundle: Contents/MacOS/<CFBundleExecutable>,
+// which is usually the bundle's own name
+fn bundle_executable(bundle: &Path) -> Option<PathBuf> {
+    let contents = bundle.join("Contents");
+    let name = fs::read_to_string(contents.join("Info.plist"))
+        .ok()
+        .and_then(|plist| plist_string(&plist, "CFBundleExecutable"))
+        .or_else(|| Some(bundle.file_stem()?.to_string_lossy().into_owned()))?;
+    let executable = contents.join("MacOS").join(name);
+    executable.is_file().then_some(executable)
+}
+
+// The file to inspect and spawn for `path`: the bundle executable for a `.app`
+// directory, otherwise `path` itself
+pub fn resolve_executable(path: &Path) -> PathBuf {
+    let is_bundle = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("app"));
+    if is_bundle && path.is_dir() {
+        if let Some(executable) = bundle_executable(path) {
+            return executable;
+        }
+    }
+    path.to_path_buf()
+}
+
+// Why a binary described by `info` won't run on `expected_platform`, or None
+// when it's consistent with it
+fn describe_mismatch(
+    path: &str,
+    expected_platform: &str,
+    info: Option<BinaryInfo>,
+) -> Result<Option<String>, String> {
+    let (os, arch) = parse_platform(expected_platform)?;
+    let mismatch = match info {
+        None => Some(format!("{} is not a recognized executable", path)),
+        Some(info) if !info.format.runs_on(os) => Some(format!(
+            "{} is {} executable, not a {} one",
+            path, info.format, expected_platform
+        )),
+        Some(BinaryInfo {
+            arch: Some(actual), ..
+        }) if arch.is_some_and(|expected| expected != actual) => Some(format!(
+            "{} is built for {:?}, not {}",
+            path, actual, expected_platform
+        )),
+        Some(_) => None,
+    };
+    Ok(mismatch)
+}
+
+// Why the executable at `path` won't run on `expected_platform`, or None when
+// it's consistent with it. `path` may be a `.app` bundle.
+async fn platform_mismatch(
+    path: String,
+    expected_platform: &str,
+) -> Result<Option<String>, String> {
+    parse_platform(expected_platform)?;
+    let file = resolve_executable(Path::new(&path));
+    if !file.is_file() {
+        return Err(format!("Executable not found: {}", path));
+    }
+
+    let info = async_runtime::spawn_blocking(move || inspect(&file))
+        .await
+        .map_err(|e| format!("Platform check task failed: {}", e))??;
+    describe_mismatch(&path, expected_platform, info)
+}
+
+// Refuse to launch e.g. a Windows build copied onto Linux
+pub async fn ensure_platform_match(path: &str, expected_platform: &str) -> Result<(), String> {
+    match platform_mismatch(path.to_string(), expected_platform).await? {
+        Some(mismatch) => Err(format!("Wrong platform: {}", mismatch)),
+        None => Ok(()),
+    }
+}
+
+// Whether the binary's format (PE, ELF, Mach-O) and architecture fit
+// `expected_platform`; the details of a mismatch are logged
+#[tauri::command]
+pub async fn check_platform_match(path: String, expected_platform: String) -> Result<bool, String> {
+    match platform_mismatch(path, &expected_platform).await? {
+        Some(mismatch) => {
+            eprintln!("[Platform] {}", mismatch);
+            Ok(false)
+        }
+        None => Ok(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SCRATCH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // `name` inside a fresh scratch directory
+    fn scratch_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "gamex-platform-{}-{}",
+            std::process::id(),
+            SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    // Write `bytes` to a scratch file and inspect it
+    fn inspect_bytes(bytes: &[u8]) -> Option<(Format, Option<Arch>)> {
+        let path = scratch_path("binary");
+        fs::write(&path, bytes).unwrap();
+        let info = inspect(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        info.map(|info| (info.format, info.arch))
+    }
+
+    fn elf(big_endian: bool, machine: u16) -> Vec<u8> {
+        let mut header = vec![0u8; 64];
+        header[..4].copy_from_slice(b"\x7fELF");
+        header[4] = 2;
+        header[5] = if big_endian { 2 } else { 1 };
+        let machine = if big_endian {
+            machine.to_be_bytes()
+        } else {
+            machine.to_le_bytes()
+        };
+        header[18..20].copy_from_slice(&machine);
+        header
+    }
+
+    fn pe(machine: u16) -> Vec<u8> {
+        let mut header = vec![0u8; 0x80];
+        header[..2].copy_from_slice(b"MZ");
+        header[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        header[0x40..0x44].copy_from_slice(b"PE\0\0");
+        header[0x44..0x46].copy_from_slice(&machine.to_le_bytes());
+        header
+    }
+
+    fn with_padding(mut header: Vec<u8>) -> Vec<u8> {
+        header.resize(64, 0);
+        header
+    }
+
+    #[test]
+    fn parses_bare_os_names_and_target_triples() {
+        assert_eq!(parse_platform("windows"), Ok((Os::Windows, None)));
+        assert_eq!(parse_platform(" Linux "), Ok((Os::Linux, None)));
+        assert_eq!(parse_platform("macos"), Ok((Os::Mac, None)));
+        assert_eq!(
+            parse_platform("x86_64-pc-windows-msvc"),
+            Ok((Os::Windows, Some(Arch::X86_64)))
+        );
+        assert_eq!(
+            parse_platform("i686-unknown-linux-gnu"),
+            Ok((Os::Linux, Some(Arch::X86)))
+        );
+        assert_eq!(
+            parse_platform("armv7-unknown-linux-gnueabihf"),
+            Ok((Os::Linux, Some(Arch::Arm)))
+        );
+        assert_eq!(
+            parse_platform("aarch64-apple-darwin"),
+            Ok((Os::Mac, Some(Arch::Aarch64)))
+        );
+        assert!(parse_platform("x86_64-unknown-freebsd").is_err());
+    }
+
+    #[test]
+    fn reads_elf_headers_in_either_byte_order() {
+        assert_eq!(
+            inspect_bytes(&elf(false, 0x3e)),
+            Some((Format::Elf, Some(Arch::X86_64)))
+        );
+        assert_eq!(
+            inspect_bytes(&elf(false, 0xb7)),
+            Some((Format::Elf, Some(Arch::Aarch64)))
+        );
+        assert_eq!(
+            inspect_bytes(&elf(true, 0x28)),
+            Some((Format::Elf, Some(Arch::Arm)))
+        );
+        // RISC-V: a Linux binary of an architecture we don't map
+        assert_eq!(inspect_bytes(&elf(false, 0xf3)), Some((Format::Elf, None)));
+    }
+
+    #[test]
+    fn reads_the_machine_from_the_pe_header() {
+        assert_eq!(
+            inspect_bytes(&pe(0x8664)),
+            Some((Format::Pe, Some(Arch::X86_64)))
+        );
+        assert_eq!(
+            inspect_bytes(&pe(0x014c)),
+            Some((Format::Pe, Some(Arch::X86)))
+        );
+        assert_eq!(
+            inspect_bytes(&pe(0xaa64)),
+            Some((Format::Pe, Some(Arch::Aarch64)))
+        );
+
+        // A DOS stub whose e_lfanew points at no PE signature
+        let mut dos_only = pe(0x8664);
+        dos_only[0x40..0x44].copy_from_slice(b"NE\0\0");
+        assert_eq!(inspect_bytes(&dos_only), None);
+    }
+
+    #[test]
+    fn reads_thin_mach_o_headers_in_either_byte_order() {
+        let mut arm64 = vec![0xcf, 0xfa, 0xed, 0xfe];
+        arm64.extend_from_slice(&0x0100_000cu32.to_le_bytes());
+        assert_eq!(
+            inspect_bytes(&with_padding(arm64)),
+            Some((Format::MachO, Some(Arch::Aarch64)))
+        );
+
+        let mut x86_64 = vec![0xcf, 0xfa, 0xed, 0xfe];
+        x86_64.extend_from_slice(&0x0100_0007u32.to_le_bytes());
+        assert_eq!(
+            inspect_bytes(&with_padding(x86_64)),
+            Some((Format::MachO, Some(Arch::X86_64)))
+        );
+
+        let mut big_endian_i386 = vec![0xfe, 0xed, 0xfa, 0xce];
+        big_endian_i386.extend_from_slice(&7u32.to_be_bytes());
+        assert_eq!(
+            inspect_bytes(&with_padding(big_endian_i386)),
+            Some((Format::MachO, Some(Arch::X86)))
+        );
+    }
+
+    #[test]
+    fn fat_binaries_are_mach_o_but_java_classes_are_not() {
+        let mut fat = vec![0xca, 0xfe, 0xba, 0xbe];
+        fat.extend_from_slice(&2u32.to_be_bytes());
+        assert_eq!(
+            inspect_bytes(&with_padding(fat)),
+            Some((Format::MachO, None))
+        );
+
+        // Minor version 0, major version 52 (Java 8)
+        let java_class = vec![0xca, 0xfe, 0xba, 0xbe, 0x00, 0x00, 0x00, 0x34];
+        assert_eq!(inspect_bytes(&with_padding(java_class)), None);
+    }
+
+    #[test]
+    fn scripts_and_unknown_files() {
+        assert_eq!(
+            inspect_bytes(b"#!/bin/sh\nexec ./game\n"),
+            Some((Format::Script, None))
+        );
+        assert_eq!(inspect_bytes(b"just some text"), None);
+        assert_eq!(inspect_bytes(b""), None);
+        // Truncated headers are unrecognized rather than an error
+        assert_eq!(inspect_bytes(b"\x7fELF"), None);
+    }
+
+    #[test]
+    fn describes_format_and_architecture_mismatches() {
+        let info = |format, arch| Some(BinaryInfo { format, arch });
+
+        let wrong_os = describe_mismatch("game.exe", "linux", info(Format::Pe, None)).unwrap();
+        assert!(wrong_os.unwrap().contains("Windows (PE)"));
+
+        let wrong_arch = describe_mismatch(
+            "game",
+            "x86_64-unknown-linux-gnu",
+            info(Format::Elf, Some(Arch::Aarch64)),
+        )
+        .unwrap();
+        assert!(wrong_arch.unwrap().contains("Aarch64"));
+
+        assert_eq!(
+            describe_mismatch("game", "linux", info(Format::Elf, Some(Arch::Aarch64))),
+            Ok(None)
+        );
+        assert_eq!(
+            describe_mismatch("Game", "aarch64-apple-darwin", info(Format::MachO, None)),
+            Ok(None)
+        );
+        assert_eq!(
+            describe_mismatch("run.sh", "macos", info(Format::Script, None)),
+            Ok(None)
+        );
+        assert!(
+            describe_mismatch("run.sh", "windows", info(Format::Script, None))
+                .unwrap()
+                .is_some()
+        );
+        assert!(describe_mismatch("notes.txt", "linux", None)
+            .unwrap()
+            .unwrap()
+            .contains("not a recognized executable"));
+    }
+
+    #[test]
+    fn reads_the_bundle_executable_from_info_plist() {
+        let plist = "<plist><dict>\n  <key>CFBundleName</key>\n  <string>Game</string>\n  \
+                     <key>CFBundleExecutable</key>\n  <string>Launcher</string>\n</dict></plist>";
+        assert_eq!(
+            plist_string(plist, "CFBundleExecutable"),
+            Some("Launcher".to_string())
+        );
+        assert_eq!(plist_string(plist, "CFBundleIdentifier"), None);
+    }
+
+    #[test]
+    fn resolves_app_bundles_to_their_executable() {
+        let bundle = scratch_path("Game.app");
+        let mac_os = bundle.join("Contents").join("MacOS");
+        fs::create_dir_all(&mac_os).unwrap();
+
+        // Without an Info.plist the executable is named after the bundle
+        fs::write(mac_os.join("Game"), b"#!/bin/sh\n").unwrap();
+        assert_eq!(resolve_executable(&bundle), mac_os.join("Game"));
+
+        fs::write(
+            bundle.join("Contents").join("Info.plist"),
+            "<dict><key>CFBundleExecutable</key><string>Launcher</string></dict>",
+        )
+        .unwrap();
+        fs::write(mac_os.join("Launcher"), b"#!/bin/sh\n").unwrap();
+        assert_eq!(resolve_executable(&bundle), mac_os.join("Launcher"));
+
+        // Plain files and bundles without their executable are left alone
+        let plain = mac_os.join("Launcher");
+        assert_eq!(resolve_executable(&plain), plain);
+        fs::remove_file(mac_os.join("Launcher")).unwrap();
+        assert_eq!(resolve_executable(&bundle), bundle);
+
+        fs::remove_dir_all(bundle.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+// Scheduling priority a user can give a running game
+#[derive(Debug, Clone, Copy)]
+pub enum Priority {
+    Low,
+    Normal,
+    High,
+}
+
+impl Priority {
+    pub fn parse(priority: &str) -> Result<Self, String> {
+        match priority.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            _ => Err(format!(
+                "Unknown priority {:?}, expected low, normal or high",
+                priority
+            )),
+        }
+    }
+}
+
+// Raising above normal usually needs elevated privileges on Unix, so `high`
+// can fail with a permission error there
+#[cfg(unix)]
+pub fn set_process_priority(pid: u32, priority: Priority) -> Result<(), String> {
+    let nice = match priority {
+        Priority::Low => 10,
+        Priority::Normal => 0,
+        Priority::High => -5,
+    };
+
+    // SAFETY: setpriority only reads its integer arguments
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+    if result != 0 {
+        return Err(format!(
+            "Failed to set priority of process {}: {}",
+            pid,
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn set_process_priority(pid: u32, priority: Priority) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+        NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+    };
+
+    let class = match priority {
+        Priority::Low => BELOW_NORMAL_PRIORITY_CLASS,
+        Priority::Normal => NORMAL_PRIORITY_CLASS,
+        Priority::High => ABOVE_NORMAL_PRIORITY_CLASS,
+    };
+
+    // SAFETY: the handle is checked for null and closed before returning
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err(format!(
+                "Failed to open process {}: {}",
+                pid,
+                std::io::Error::last_os_error()
+            ));
+        }
+        let ok = SetPriorityClass(handle, class) != 0;
+        let error = std::io::Error::last_os_error();
+        CloseHandle(handle);
+        if !ok {
+            return Err(format!(
+                "Failed to set priority of process {}: {}",
+                pid, error
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn set_process_priority(_pid: u32, _priority: Priority) -> Result<(), String> {
+    Err("Process priority is not supported on this platform".to_string())
+}
@@ -0,0 +1,310 @@
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri_plugin_http::reqwest;
+
+use crate::download::{cid_size, fetch_chunk, CHUNK_SIZE};
+
+// Custom URI scheme serving IPFS content: gamex-ipfs://localhost/<cid>
+pub const PROXY_SCHEME: &str = "gamex-ipfs";
+
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    // No (usable) Range header: serve the whole body
+    Full,
+    // Inclusive byte range
+    Partial { start: u64, end: u64 },
+    Unsatisfiable,
+}
+
+// Parse a single-range `bytes=` header. Malformed or multi-range headers are
+// ignored and the full body is served, which RFC 9110 permits.
+fn parse_range(value: Option<&str>, size: u64) -> ByteRange {
+    let Some(spec) = value.and_then(|value| value.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = spec.split_once('-') else {
+        return ByteRange::Full;
+    };
+
+    match (start.trim(), end.trim()) {
+        // Suffix range: the last N bytes
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => ByteRange::Unsatisfiable,
+            Ok(_) if size == 0 => ByteRange::Unsatisfiable,
+            Ok(length) => ByteRange::Partial {
+                start: size.saturating_sub(length),
+                end: size - 1,
+            },
+            Err(_) => ByteRange::Full,
+        },
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return ByteRange::Full;
+            };
+            if start >= size {
+                return ByteRange::Unsatisfiable;
+            }
+            let end = if end.is_empty() {
+                size - 1
+            } else {
+                match end.parse::<u64>() {
+                    Ok(end) if end >= start => end.min(size - 1),
+                    _ => return ByteRange::Full,
+                }
+            };
+            ByteRange::Partial { start, end }
+        }
+    }
+}
+
+// Status and half-open byte span to answer `range` with, or the status to
+// reject it with. Every response is capped at CHUNK_SIZE so no request
+// buffers a multi-GB file in memory. A 206 is only valid in reply to a
+// Range request, so a file larger than that without one is refused rather
+// than silently truncated; players fetch it by range.
+fn response_span(range: &ByteRange, size: u64) -> Result<(StatusCode, u64, u64), StatusCode> {
+    match *range {
+        ByteRange::Unsatisfiable => Err(StatusCode::RANGE_NOT_SATISFIABLE),
+        ByteRange::Full if size <= CHUNK_SIZE => Ok((StatusCode::OK, 0, size)),
+        ByteRange::Full => Err(StatusCode::PAYLOAD_TOO_LARGE),
+        ByteRange::Partial { start, end } => Ok((
+            StatusCode::PARTIAL_CONTENT,
+            start,
+            (end + 1).min(start.saturating_add(CHUNK_SIZE)),
+        )),
+    }
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(message.into_bytes())
+        .unwrap_or_default()
+}
+
+// Serve a proxied IPFS request, translating HTTP Range into `cat` offset/length
+pub async fn handle(
+    client: reqwest::Client,
+    api_url: String,
+    request: Request<Vec<u8>>,
+) -> Response<Vec<u8>> {
+    let cid = request.uri().path().trim_matches('/').to_string();
+    if cid.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "Missing CID".to_string());
+    }
+
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let size = match cid_size(&client, &api_url, &cid).await {
+        Ok(size) => size,
+        Err(e) => return error_response(StatusCode::BAD_GATEWAY, e),
+    };
+
+    let range = parse_range(
+        request
+            .headers()
+            .get(header::RANGE)
+            .and_then(|value| value.to_str().ok()),
+        size,
+    );
+
+    let (status, start, end) = match response_span(&range, size) {
+        Ok(span) => span,
+        Err(StatusCode::RANGE_NOT_SATISFIABLE) => {
+            return Response::builder()
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(header::CONTENT_RANGE, format!("bytes */{}", size))
+                .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+                .body(Vec::new())
+                .unwrap_or_default();
+        }
+        Err(status) => {
+            let mut response = error_response(
+                status,
+                format!(
+                    "{} is {} bytes; request it with a Range header, up to {} bytes at a time",
+                    cid, size, CHUNK_SIZE
+                ),
+            );
+            response.headers_mut().insert(
+                header::ACCEPT_RANGES,
+                header::HeaderValue::from_static("bytes"),
+            );
+            return response;
+        }
+    };
+
+    let body = if end > start {
+        match fetch_chunk(
+            &client,
+            &api_url,
+            &cid,
+            start,
+            end - start,
+            authorization.as_deref(),
+        )
+        .await
+        {
+            Ok(body) => body,
+            Err(e) => return error_response(StatusCode::BAD_GATEWAY, e),
+        }
+    } else {
+        Vec::new()
+    };
+
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CONTENT_LENGTH, body.len())
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    if status == StatusCode::PARTIAL_CONTENT {
+        response = response.header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end - 1, size),
+        );
+    }
+
+    response.body(body).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: u64 = 10_000;
+
+    #[test]
+    fn mid_file_range() {
+        assert_eq!(
+            parse_range(Some("bytes=1000-1999"), SIZE),
+            ByteRange::Partial {
+                start: 1000,
+                end: 1999
+            }
+        );
+    }
+
+    #[test]
+    fn open_ended_range_runs_to_the_end() {
+        assert_eq!(
+            parse_range(Some("bytes=9000-"), SIZE),
+            ByteRange::Partial {
+                start: 9000,
+                end: SIZE - 1
+            }
+        );
+    }
+
+    #[test]
+    fn suffix_range_is_the_last_bytes() {
+        assert_eq!(
+            parse_range(Some("bytes=-500"), SIZE),
+            ByteRange::Partial {
+                start: 9500,
+                end: SIZE - 1
+            }
+        );
+        // Longer than the file: the whole file
+        assert_eq!(
+            parse_range(Some("bytes=-50000"), SIZE),
+            ByteRange::Partial {
+                start: 0,
+                end: SIZE - 1
+            }
+        );
+    }
+
+    #[test]
+    fn end_past_the_file_is_clamped() {
+        assert_eq!(
+            parse_range(Some("bytes=9990-20000"), SIZE),
+            ByteRange::Partial {
+                start: 9990,
+                end: SIZE - 1
+            }
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_ranges() {
+        assert_eq!(
+            parse_range(Some("bytes=10000-"), SIZE),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(
+            parse_range(Some("bytes=-0"), SIZE),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(parse_range(Some("bytes=-10"), 0), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn unusable_headers_serve_the_full_body() {
+        for header in [
+            None,
+            Some("items=0-10"),
+            Some("bytes=0-10,20-30"),
+            Some("bytes=abc-"),
+            Some("bytes=500-100"),
+            Some("bytes=5"),
+        ] {
+            assert_eq!(parse_range(header, SIZE), ByteRange::Full, "{:?}", header);
+        }
+    }
+
+    #[test]
+    fn small_files_are_served_whole() {
+        assert_eq!(
+            response_span(&ByteRange::Full, SIZE),
+            Ok((StatusCode::OK, 0, SIZE))
+        );
+    }
+
+    #[test]
+    fn large_files_are_served_a_chunk_at_a_time() {
+        let size = 3 * CHUNK_SIZE;
+        let open_ended = parse_range(Some("bytes=100-"), size);
+        assert_eq!(
+            response_span(&open_ended, size),
+            Ok((StatusCode::PARTIAL_CONTENT, 100, 100 + CHUNK_SIZE))
+        );
+        // Short ranges are served exactly
+        let mid_file = parse_range(Some("bytes=1000-1999"), size);
+        assert_eq!(
+            response_span(&mid_file, size),
+            Ok((StatusCode::PARTIAL_CONTENT, 1000, 2000))
+        );
+        assert_eq!(
+            response_span(&ByteRange::Unsatisfiable, size),
+            Err(StatusCode::RANGE_NOT_SATISFIABLE)
+        );
+    }
+
+    #[test]
+    fn large_files_are_not_truncated_without_a_range() {
+        let size = CHUNK_SIZE + 1;
+        assert_eq!(
+            response_span(&ByteRange::Full, size),
+            Err(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+        // Malformed Range headers are ignored, so they're refused the same way
+        assert_eq!(
+            response_span(&parse_range(Some("bytes=0-10,20-30"), size), size),
+            Err(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+        assert_eq!(
+            response_span(&ByteRange::Full, CHUNK_SIZE),
+            Ok((StatusCode::OK, 0, CHUNK_SIZE))
+        );
+    }
+}
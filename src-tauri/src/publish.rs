@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use tauri::{async_runtime, AppHandle, Emitter, Manager};
+
+use crate::integrity::sha256_file;
+use crate::ipfs::{self, IPFS_GATEWAY_URL, PUBLIC_GATEWAY_URL};
+use crate::{api, http, redact, ProcessManager};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PublishExecutable {
+    pub platform: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PublishRequest {
+    pub collection_address: String,
+    pub candy_machine_address: String,
+    pub name: String,
+    pub description: String,
+    pub image_path: String,
+    pub categories: Vec<String>,
+    pub executables: Vec<PublishExecutable>,
+    pub creator: String,
+    pub metadata_uri: String,
+    pub price_lamports: i64,
+}
+
+#[derive(Clone, Serialize)]
+struct PublishProgress {
+    phase: &'static str,
+    detail: String,
+}
+
+fn emit_progress(app_handle: &AppHandle, phase: &'static str, detail: String) {
+    println!("[Publish] {}: {}", phase, detail);
+    let _ = app_handle.emit("publish-progress", PublishProgress { phase, detail });
+}
+
+// Unpin everything added so far when a later step fails
+async fn rollback_pins(app_handle: &AppHandle, cids: &[String]) {
+    for cid in cids {
+        if let Err(e) = ipfs::unpin(app_handle, cid).await {
+            eprintln!("[Publish] Failed to roll back pin {}: {}", cid, e);
+        }
+    }
+}
+
+async fn publish_assets(
+    app_handle: &AppHandle,
+    req: &PublishRequest,
+    pinned: &mut Vec<String>,
+) -> Result<serde_json::Value, String> {
+    let mut executables = Vec::new();
+
+    for executable in &req.executables {
+        emit_progress(app_handle, "hash", executable.platform.clone());
+        let path = PathBuf::from(&executable.path);
+        let (sha256, size_bytes) = async_runtime::spawn_blocking(move || sha256_file(&path))
+            .await
+            .map_err(|e| format!("Hashing task failed: {}", e))??;
+
+        emit_progress(app_handle, "upload-executable", executable.platform.clone());
+        let cid = ipfs::add_file(app_handle, &executable.path).await?;
+        pinned.push(cid.clone());
+
+        executables.push(json!({
+            "platform": executable.platform,
+            "url": ipfs::gateway_url(IPFS_GATEWAY_URL, &cid),
+            "sha256": sha256,
+            "size_bytes": size_bytes,
+        }));
+    }
+
+    emit_progress(app_handle, "upload-image", req.image_path.clone());
+    let image_cid = ipfs::add_file(app_handle, &req.image_path).await?;
+    pinned.push(image_cid.clone());
+
+    Ok(json!({
+        "collection_address": req.collection_address,
+        "candy_machine_address": req.candy_machine_address,
+        "name": req.name,
+        "description": req.description,
+        "image_url": ipfs::gateway_url(PUBLIC_GATEWAY_URL, &image_cid),
+        "categories": req.categories,
+        "executables": executables,
+        "creator": req.creator,
+        "metadata_uri": req.metadata_uri,
+        "price_lamports": req.price_lamports,
+    }))
+}
+
+// Upload a game's files to IPFS and register it with the backend, returning its id
+#[tauri::command]
+pub async fn publish_game(req: PublishRequest, app_handle: AppHandle) -> Result<String, String> {
+    app_handle
+        .state::<ProcessManager>()
+        .ensure_ipfs_available()?;
+
+    if req.executables.is_empty() {
+        return Err("At least one executable is required".to_string());
+    }
+
+    let mut pinned = Vec::new();
+
+    let create_request = match publish_assets(&app_handle, &req, &mut pinned).await {
+        Ok(request) => request,
+        Err(e) => {
+            rollback_pins(&app_handle, &pinned).await;
+            return Err(e);
+        }
+    };
+
+    println!(
+        "[Publish] Registering {} for creator {}",
+        req.name,
+        redact::address(&req.creator)
+    );
+    emit_progress(&app_handle, "register", req.name.clone());
+    let client = http::client(&app_handle);
+    let game = match api::create_game(&client, &api::base_url(&app_handle), &create_request).await {
+        Ok(game) => game,
+        Err(e) => {
+            emit_progress(&app_handle, "rollback", e.clone());
+            rollback_pins(&app_handle, &pinned).await;
+            return Err(e);
+        }
+    };
+
+    let id = game.id.map(|id| id.oid).unwrap_or_default();
+    emit_progress(&app_handle, "done", id.clone());
+    Ok(id)
+}
@@ -0,0 +1,60 @@
+// Wallet redaction for log lines. The API and the launcher share no library
+// crate, so this is kept identical to api/src/redact.rs; change both together.
+use std::sync::OnceLock;
+
+// Characters kept from each end of a redacted address
+const VISIBLE_CHARS: usize = 4;
+
+// Local dev escape hatch: DEBUG_FULL_ADDRESSES=true logs addresses untouched
+fn full_addresses() -> bool {
+    static FULL: OnceLock<bool> = OnceLock::new();
+    *FULL.get_or_init(|| {
+        std::env::var("DEBUG_FULL_ADDRESSES")
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    })
+}
+
+// Shorten a wallet or other pubkey for log output, e.g. `abcd…wxyz`. Only
+// for logs; responses and stored data keep the full address.
+pub fn address(address: &str) -> String {
+    if full_addresses() {
+        return address.to_string();
+    }
+    shorten(address)
+}
+
+fn shorten(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= VISIBLE_CHARS * 2 {
+        return address.to_string();
+    }
+    let head: String = chars[..VISIBLE_CHARS].iter().collect();
+    let tail: String = chars[chars.len() - VISIBLE_CHARS..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_keep_both_ends() {
+        assert_eq!(
+            shorten("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+            "9WzD…AWWM"
+        );
+        assert_eq!(shorten("123456789"), "1234…6789");
+    }
+
+    #[test]
+    fn short_values_are_left_alone() {
+        assert_eq!(shorten("12345678"), "12345678");
+        assert_eq!(shorten(""), "");
+    }
+
+    #[test]
+    fn multibyte_characters_are_not_split() {
+        assert_eq!(shorten("ééééxéééé"), "éééé…éééé");
+    }
+}
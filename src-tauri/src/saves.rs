@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_opener::OpenerExt;
+
+// Passed to launched games so they know where to keep their saves
+pub const SAVE_DIR_ENV: &str = "GAMEX_SAVE_DIR";
+
+// Per-game save directory under the app data dir, created if needed
+pub fn save_dir(app_handle: &AppHandle, game_id: &str) -> Result<PathBuf, String> {
+    // Game ids are ObjectId hex strings; anything else could escape the saves dir
+    if game_id.is_empty() || !game_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid game id: {}", game_id));
+    }
+
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?
+        .join("saves")
+        .join(game_id);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create save folder {:?}: {}", dir, e))?;
+    Ok(dir)
+}
+
+#[tauri::command]
+pub fn open_save_folder(game_id: String, app_handle: AppHandle) -> Result<(), String> {
+    let dir = save_dir(&app_handle, &game_id)?;
+    app_handle
+        .opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open save folder: {}", e))
+}
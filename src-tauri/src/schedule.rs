@@ -0,0 +1,107 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{async_runtime, AppHandle, Emitter, Manager};
+
+// Launches waiting on a timer, keyed by schedule id
+#[derive(Default)]
+pub struct ScheduledLaunches {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<String, async_runtime::JoinHandle<()>>>,
+}
+
+impl ScheduledLaunches {
+    // Drop every pending launch, e.g. on exit
+    pub fn cancel_all(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            for (_, task) in pending.drain() {
+                task.abort();
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct ScheduledLaunchFired {
+    id: String,
+    path: String,
+    // Launch error, if the game failed to start
+    error: Option<String>,
+}
+
+// Launch `path` after `delay_ms`, for "launch at a time" or staggered starts.
+// Returns an id for `cancel_scheduled_launch`; `scheduled-launch-fired` is
+// emitted once the launch has been attempted.
+#[tauri::command]
+pub fn schedule_launch(
+    path: String,
+    delay_ms: u64,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let schedules = app_handle.state::<ScheduledLaunches>();
+    let id = format!(
+        "launch-{}",
+        schedules.next_id.fetch_add(1, Ordering::SeqCst) + 1
+    );
+
+    // Held so the task can't remove its entry before it's been inserted
+    let mut pending = schedules
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to schedule launch: {}", e))?;
+
+    let task_id = id.clone();
+    let task_handle = app_handle.clone();
+    let task = async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        if let Ok(mut pending) = task_handle.state::<ScheduledLaunches>().pending.lock() {
+            pending.remove(&task_id);
+        }
+
+        println!("[Schedule] Firing {} for {}", task_id, path);
+        let result = crate::execute_game(
+            path.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            task_handle.clone(),
+        )
+        .await;
+        let _ = task_handle.emit(
+            "scheduled-launch-fired",
+            ScheduledLaunchFired {
+                id: task_id,
+                path,
+                error: result.err(),
+            },
+        );
+    });
+    pending.insert(id.clone(), task);
+
+    println!("[Schedule] Scheduled {} in {} ms", id, delay_ms);
+    Ok(id)
+}
+
+// Cancel a pending scheduled launch; false if it already fired or never existed
+#[tauri::command]
+pub fn cancel_scheduled_launch(id: String, app_handle: AppHandle) -> bool {
+    let task = app_handle
+        .state::<ScheduledLaunches>()
+        .pending
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.remove(&id));
+
+    match task {
+        Some(task) => {
+            task.abort();
+            println!("[Schedule] Cancelled {}", id);
+            true
+        }
+        None => false,
+    }
+}
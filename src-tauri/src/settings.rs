@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::{AppHandle, Manager};
+
+use crate::{api, ProcessManager};
+
+const SETTINGS_FILE: &str = "gamex.toml";
+
+// Launcher settings persisted to gamex.toml in the app config dir. Keys
+// missing from the file fall back to their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub ipfs_api_port: u16,
+    pub ipfs_gateway_port: u16,
+    // Start the daemon with --offline (no network, local content only)
+    pub offline_mode: bool,
+    // Public gateways tried when neither the daemon nor the API is reachable
+    pub gateways: Vec<String>,
+    pub max_concurrent_games: Option<usize>,
+    pub api_base_url: String,
+    // Reuse a daemon already answering on the API port (e.g. left over after
+    // a crash) instead of shutting it down and starting our own
+    pub adopt_existing_daemon: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            ipfs_api_port: 5001,
+            ipfs_gateway_port: 8080,
+            offline_mode: false,
+            gateways: vec![
+                "https://ipfs.io".to_string(),
+                "https://dweb.link".to_string(),
+            ],
+            max_concurrent_games: None,
+            api_base_url: api::API_BASE_URL.to_string(),
+            adopt_existing_daemon: true,
+        }
+    }
+}
+
+impl Settings {
+    pub fn ipfs_api_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.ipfs_api_port)
+    }
+
+    pub fn ipfs_gateway_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.ipfs_gateway_port)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.ipfs_api_port == 0 || self.ipfs_gateway_port == 0 {
+            return Err("IPFS ports must be non-zero".to_string());
+        }
+        if self.ipfs_api_port == self.ipfs_gateway_port {
+            return Err("IPFS API and gateway ports must differ".to_string());
+        }
+        if self.max_concurrent_games == Some(0) {
+            return Err("Max concurrent games must be at least 1".to_string());
+        }
+        if !self.api_base_url.starts_with("http://") && !self.api_base_url.starts_with("https://") {
+            return Err("API base URL must be http(s)".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub struct SettingsState {
+    // None when the config dir can't be resolved; settings then live in memory only
+    path: Option<PathBuf>,
+    settings: RwLock<Settings>,
+}
+
+impl SettingsState {
+    pub fn load(app_handle: &AppHandle) -> Self {
+        let path = app_handle
+            .path()
+            .app_config_dir()
+            .map(|dir| dir.join(SETTINGS_FILE))
+            .map_err(|e| eprintln!("[Settings] Failed to resolve config dir: {}", e))
+            .ok();
+
+        let settings = path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(contents) => Some(contents),
+                Err(e) => {
+                    eprintln!("[Settings] Failed to read {:?}: {}", path, e);
+                    None
+                }
+            })
+            .and_then(|contents| match toml::from_str::<Settings>(&contents) {
+                Ok(settings) => Some(settings),
+                Err(e) => {
+                    eprintln!(
+                        "[Settings] Invalid {}, using defaults: {}",
+                        SETTINGS_FILE, e
+                    );
+                    None
+                }
+            })
+            .filter(|settings| match settings.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("[Settings] Invalid settings, using defaults: {}", e);
+                    false
+                }
+            })
+            .unwrap_or_default();
+
+        println!("[Settings] Loaded settings: {:?}", settings);
+        Self {
+            path,
+            settings: RwLock::new(settings),
+        }
+    }
+
+    pub fn get(&self) -> Settings {
+        self.settings
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, settings: Settings) -> Result<(), String> {
+        settings.validate()?;
+
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create config dir: {}", e))?;
+            }
+            let contents = toml::to_string_pretty(&settings)
+                .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+            // Write then rename so a crash can't leave a truncated file behind
+            let tmp = path.with_extension("toml.tmp");
+            std::fs::write(&tmp, contents)
+                .map_err(|e| format!("Failed to write settings: {}", e))?;
+            std::fs::rename(&tmp, path).map_err(|e| format!("Failed to save settings: {}", e))?;
+        }
+
+        let mut current = self
+            .settings
+            .write()
+            .map_err(|_| "Settings lock poisoned".to_string())?;
+        *current = settings;
+        Ok(())
+    }
+}
+
+pub fn current(app_handle: &AppHandle) -> Settings {
+    app_handle.state::<SettingsState>().get()
+}
+
+// Apply a change to the current settings and persist the result
+pub fn update(
+    app_handle: &AppHandle,
+    change: impl FnOnce(&mut Settings),
+) -> Result<Settings, String> {
+    let state = app_handle.state::<SettingsState>();
+    let mut settings = state.get();
+    let previous_api_base_url = settings.api_base_url.clone();
+    change(&mut settings);
+    state.save(settings.clone())?;
+    apply(app_handle, &settings);
+
+    // Startup may have used GAMEX_API_URL instead; an explicit change wins
+    if settings.api_base_url != previous_api_base_url {
+        app_handle
+            .state::<api::ApiConfig>()
+            .set(&settings.api_base_url);
+    }
+    Ok(settings)
+}
+
+// Push settings that have live effects into the running app
+pub fn apply(app_handle: &AppHandle, settings: &Settings) {
+    app_handle
+        .state::<ProcessManager>()
+        .set_max_concurrent_games(settings.max_concurrent_games);
+}
+
+#[tauri::command]
+pub fn get_settings(app_handle: AppHandle) -> Settings {
+    current(&app_handle)
+}
+
+// Merge the given keys into the current settings and persist them. IPFS port
+// and offline changes apply on the next daemon start.
+#[tauri::command]
+pub fn set_settings(patch: serde_json::Value, app_handle: AppHandle) -> Result<Settings, String> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err("Settings must be an object".to_string());
+    };
+
+    let mut merged = serde_json::to_value(current(&app_handle))
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let serde_json::Value::Object(fields) = &mut merged {
+        fields.extend(patch);
+    }
+
+    let merged: Settings =
+        serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))?;
+    update(&app_handle, |settings| *settings = merged)
+}
@@ -0,0 +1,261 @@
+use serde::Serialize;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use sysinfo::{Disk, Disks};
+use tauri::{async_runtime, AppHandle, Manager};
+
+// Roots games must never be installed under (checked after resolving symlinks)
+#[cfg(target_os = "windows")]
+const PROTECTED_ROOTS: &[&str] = &[
+    "C:\\Windows",
+    "C:\\Program Files",
+    "C:\\Program Files (x86)",
+    "C:\\ProgramData",
+];
+#[cfg(target_os = "macos")]
+const PROTECTED_ROOTS: &[&str] = &[
+    "/System",
+    "/Library",
+    "/bin",
+    "/sbin",
+    "/usr",
+    "/private/etc",
+    "/private/var/db",
+];
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const PROTECTED_ROOTS: &[&str] = &[
+    "/bin", "/boot", "/dev", "/etc", "/lib", "/lib64", "/proc", "/sbin", "/sys", "/usr", "/var",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskSpace {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+// Closest ancestor of `path` (or itself) that exists, canonicalized
+fn nearest_existing(path: &Path) -> Result<PathBuf, String> {
+    let mut current = Some(path);
+    while let Some(candidate) = current {
+        if candidate.exists() {
+            return candidate
+                .canonicalize()
+                .map_err(|e| format!("Failed to resolve {:?}: {}", candidate, e));
+        }
+        current = candidate.parent();
+    }
+    Err(format!("No existing ancestor for {:?}", path))
+}
+
+// The volume is the disk with the longest mount point containing the path
+fn containing_disk<'a>(disks: &'a Disks, resolved: &Path) -> Option<&'a Disk> {
+    disks
+        .list()
+        .iter()
+        .filter(|disk| resolved.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+}
+
+pub fn disk_space_for(path: &Path) -> Result<DiskSpace, String> {
+    let resolved = nearest_existing(path)?;
+    let disks = Disks::new_with_refreshed_list();
+
+    containing_disk(&disks, &resolved)
+        .map(|disk| DiskSpace {
+            total_bytes: disk.total_space(),
+            available_bytes: disk.available_space(),
+        })
+        .ok_or_else(|| format!("No disk found for {:?}", resolved))
+}
+
+#[tauri::command]
+pub fn disk_space(path: String) -> Result<DiskSpace, String> {
+    disk_space_for(Path::new(&path))
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstallSize {
+    pub path: String,
+    pub size_bytes: u64,
+    // The directory doesn't exist; reported as zero bytes
+    pub missing: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VolumeUsage {
+    pub mount_point: String,
+    // Bytes used by the given installs on this volume
+    pub library_bytes: u64,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LibraryStorage {
+    // In the order the install dirs were given
+    pub installs: Vec<InstallSize>,
+    pub total_bytes: u64,
+    // Each distinct volume holding at least one existing install
+    pub volumes: Vec<VolumeUsage>,
+}
+
+// Total size of the files under `dir`. Symlinks aren't followed (so links
+// into shared data aren't counted twice) and unreadable entries are skipped.
+fn dir_size(dir: &Path) -> u64 {
+    let mut size = 0;
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("[Storage] Skipping {:?}: {}", dir, e);
+                continue;
+            }
+        };
+        for entry in entries {
+            match entry.and_then(|entry| Ok((entry.path(), entry.metadata()?))) {
+                Ok((path, metadata)) if metadata.is_dir() => pending.push(path),
+                Ok((_, metadata)) if metadata.is_file() => size += metadata.len(),
+                Ok(_) => {}
+                Err(e) => eprintln!("[Storage] Skipping entry in {:?}: {}", dir, e),
+            }
+        }
+    }
+    size
+}
+
+fn library_storage_for(install_dirs: Vec<String>) -> LibraryStorage {
+    let disks = Disks::new_with_refreshed_list();
+    let mut installs = Vec::new();
+    let mut volumes: Vec<VolumeUsage> = Vec::new();
+
+    for path in install_dirs {
+        let dir = PathBuf::from(&path);
+        if !dir.is_dir() {
+            installs.push(InstallSize {
+                path,
+                size_bytes: 0,
+                missing: true,
+            });
+            continue;
+        }
+
+        let size_bytes = dir_size(&dir);
+        let disk = dir
+            .canonicalize()
+            .ok()
+            .and_then(|resolved| containing_disk(&disks, &resolved));
+        if let Some(disk) = disk {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            match volumes
+                .iter_mut()
+                .find(|volume| volume.mount_point == mount_point)
+            {
+                Some(volume) => volume.library_bytes += size_bytes,
+                None => volumes.push(VolumeUsage {
+                    mount_point,
+                    library_bytes: size_bytes,
+                    total_bytes: disk.total_space(),
+                    available_bytes: disk.available_space(),
+                }),
+            }
+        }
+
+        installs.push(InstallSize {
+            path,
+            size_bytes,
+            missing: false,
+        });
+    }
+
+    LibraryStorage {
+        total_bytes: installs.iter().map(|install| install.size_bytes).sum(),
+        installs,
+        volumes,
+    }
+}
+
+// Disk usage for the storage management screen: each install's size, the
+// library total, and free space on every volume the library spans
+#[tauri::command]
+pub async fn library_storage(install_dirs: Vec<String>) -> Result<LibraryStorage, String> {
+    let storage = async_runtime::spawn_blocking(move || library_storage_for(install_dirs))
+        .await
+        .map_err(|e| format!("Storage scan failed: {}", e))?;
+    println!(
+        "[Storage] {} install(s) use {} bytes across {} volume(s)",
+        storage.installs.len(),
+        storage.total_bytes,
+        storage.volumes.len()
+    );
+    Ok(storage)
+}
+
+fn protected_root(path: &Path) -> Option<&'static str> {
+    PROTECTED_ROOTS.iter().copied().find(|root| {
+        #[cfg(target_os = "windows")]
+        {
+            // Windows paths are case-insensitive and canonicalize to \\?\ form
+            let path = path.to_string_lossy().to_lowercase();
+            let path = path.trim_start_matches(r"\\?\");
+            let root = root.to_lowercase();
+            path == root || path.starts_with(&format!("{}\\", root))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            path.starts_with(root)
+        }
+    })
+}
+
+// Check an install directory is usable before downloading into it: creates it
+// if needed, then writes and removes a probe file
+#[tauri::command]
+pub fn validate_install_dir(path: String, app_handle: AppHandle) -> Result<(), String> {
+    let dir = PathBuf::from(&path);
+    if !dir.is_absolute() {
+        return Err(format!(
+            "Install directory must be an absolute path: {}",
+            path
+        ));
+    }
+
+    // The app's own data dir is always allowed, even where it sits under a protected root
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .ok()
+        .and_then(|dir| dir.canonicalize().ok());
+    let resolved = nearest_existing(&dir)?;
+    let inside_app_data = app_data_dir.is_some_and(|app_dir| resolved.starts_with(app_dir));
+    if let Some(root) = protected_root(&resolved).filter(|_| !inside_app_data) {
+        return Err(format!(
+            "Install directory is inside a protected system location ({}): {}",
+            root, path
+        ));
+    }
+
+    if dir.exists() && !dir.is_dir() {
+        return Err(format!("Install path is not a directory: {}", path));
+    }
+
+    std::fs::create_dir_all(&dir).map_err(|e| match e.kind() {
+        ErrorKind::PermissionDenied => {
+            format!("Permission denied creating install directory {}", path)
+        }
+        ErrorKind::NotFound => format!("Parent directory does not exist for {}", path),
+        _ => format!("Failed to create install directory {}: {}", path, e),
+    })?;
+
+    let probe = dir.join(format!(".gamex-write-probe-{}", std::process::id()));
+    std::fs::write(&probe, b"").map_err(|e| match e.kind() {
+        ErrorKind::PermissionDenied => format!("Install directory is not writable: {}", path),
+        _ => format!("Failed to write to install directory {}: {}", path, e),
+    })?;
+    std::fs::remove_file(&probe)
+        .map_err(|e| format!("Failed to remove probe file from {}: {}", path, e))?;
+
+    println!("[Storage] Install directory validated: {}", path);
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::ipfs::extract_cid;
+use crate::library::InstalledGame;
+use crate::{api, http};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub game_id: String,
+    pub current_cid: String,
+    pub latest_cid: String,
+    pub url: String,
+}
+
+// Compare installed CIDs against the latest published build for this platform
+#[tauri::command]
+pub async fn check_updates(
+    installed: Vec<InstalledGame>,
+    app_handle: AppHandle,
+) -> Result<Vec<UpdateInfo>, String> {
+    let client = http::client(&app_handle);
+    let ids: Vec<String> = installed.iter().map(|game| game.game_id.clone()).collect();
+
+    let games: HashMap<String, api::ApiGame> =
+        api::fetch_games_batch(&client, &api::base_url(&app_handle), &ids)
+            .await?
+            .into_iter()
+            .filter_map(|game| game.id.clone().map(|id| (id.oid, game)))
+            .collect();
+
+    let platform = crate::current_platform();
+    let mut updates = Vec::new();
+
+    for game in installed {
+        let Some(executable) = games
+            .get(&game.game_id)
+            .and_then(|latest| latest.executable_for(platform))
+        else {
+            continue;
+        };
+
+        let Some(latest_cid) = extract_cid(&executable.url) else {
+            eprintln!(
+                "[Updates] Could not extract CID for {} from {}",
+                game.game_id, executable.url
+            );
+            continue;
+        };
+
+        if latest_cid != game.current_cid {
+            updates.push(UpdateInfo {
+                game_id: game.game_id,
+                current_cid: game.current_cid,
+                latest_cid,
+                url: executable.url.clone(),
+            });
+        }
+    }
+
+    println!("[Updates] {} update(s) available", updates.len());
+    Ok(updates)
+}
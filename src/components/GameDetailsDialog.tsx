@@ -10,7 +10,7 @@ import { Button } from "@/components/ui/button";
 import { Badge } from "@/components/ui/badge";
 import { Copy, Calendar, User } from "lucide-react";
 import { formatDate } from "@/lib/utils/date-format";
-import { lamportsToSol } from "@/lib/blockchain/utils/currency";
+import { formatPrice } from "@/lib/blockchain/utils/currency";
 import type { CreateGameResponse } from "@/lib/api/types";
 import { useState } from "react";
 
@@ -35,9 +35,7 @@ export function GameDetailsDialog({
 
   if (!game) return null;
 
-  const priceInSol = lamportsToSol(game.price_lamports);
-  const formattedPrice =
-    priceInSol === 0 ? "Grátis" : `${priceInSol.toFixed(2)} SOL`;
+  const formattedPrice = formatPrice(game.price_lamports, game.price_currency);
 
   const handleCopyAddress = async () => {
     try {
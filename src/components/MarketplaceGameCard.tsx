@@ -2,7 +2,7 @@ import { ShoppingCart } from "lucide-react";
 import { cn } from "@/lib/utils";
 import { Button } from "@/components/ui/button";
 import { Badge } from "@/components/ui/badge";
-import { lamportsToSol } from "@/lib/blockchain/utils/currency";
+import { formatPrice } from "@/lib/blockchain/utils/currency";
 import type { CreateGameResponse } from "@/lib/api/types";
 import { TARGET_TRIPLES, getPlatformFamilies } from "@/lib/platform";
 
@@ -40,10 +40,8 @@ export function MarketplaceGameCard({
     return `http://127.0.0.1:8080/ipfs/${cid}`;
   };
 
-  // Convert lamports to SOL and format
-  const priceInSol = lamportsToSol(game.price_lamports);
-  const formattedPrice =
-    priceInSol === 0 ? "Grátis" : `${priceInSol.toFixed(2)} SOL`;
+  // Format in the listing currency (SOL converts from lamports)
+  const formattedPrice = formatPrice(game.price_lamports, game.price_currency);
 
   // Extract platform families from executables array
   const platforms = game.executables?.map((exec) => exec.platform) || [];
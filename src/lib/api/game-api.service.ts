@@ -126,11 +126,9 @@ export class GameApiService {
           params.set("q", filters.name);
         }
 
-        // Add category filters
+        // Add category filters (comma-separated, the API can't read repeated keys)
         if (filters.categories && filters.categories.length > 0) {
-          filters.categories.forEach((category) => {
-            params.append("categories", category);
-          });
+          params.set("categories", filters.categories.join(","));
         }
 
         // Convert SOL to lamports for price filters
@@ -17,10 +17,13 @@ export interface CreateGameRequest {
   description: string;
   image_url: string;
   categories: string[];
+  tags?: string[]; // free-form labels, lowercased by the API
   executables: GameExecutable[];
   creator: string;
   metadata_uri: string;
-  price_lamports: number;
+  price_lamports: number; // lamports for SOL, token base units otherwise
+  price_currency?: string; // defaults to "SOL"
+  price_mint?: string | null; // SPL token mint when not priced in SOL
 }
 
 /**
@@ -33,10 +36,13 @@ export interface CreateGameResponse {
   description: string;
   image_url: string;
   categories: string[];
+  tags?: string[];
   executables: GameExecutable[];
   creator: string;
   metadata_uri: string;
   price_lamports: number;
+  price_currency?: string;
+  price_mint?: string | null;
   created_at: string;
 }
 
@@ -51,3 +51,18 @@ export function parseSolToLamports(solString: string): bigint {
   }
   return solToLamports(sol);
 }
+
+/**
+ * Formats a game price in its listing currency
+ * @param amount - Price in lamports for SOL, or token base units otherwise
+ * @param currency - Currency code (default: "SOL")
+ * @returns "Grátis" for free games, otherwise the amount with its currency
+ * @example formatPrice(1500000000, "SOL") => "1.50 SOL"
+ */
+export function formatPrice(amount: number, currency: string = 'SOL'): string {
+  if (amount === 0) {
+    return 'Grátis';
+  }
+  // Only SOL has a known decimal scale; token prices are shown in base units
+  return currency === 'SOL' ? formatSol(amount) : `${amount} ${currency}`;
+}